@@ -0,0 +1,300 @@
+//! Closed-form distributions to validate simulations against.
+//!
+//! Probabilities are computed in log space so that large parameters neither overflow nor lose
+//! all precision.
+
+use crate::pmf::Pmf;
+
+pub trait Distribution {
+    /// $P(X = x)$
+    fn prob(&self, x: usize) -> f64;
+    fn mean(&self) -> f64;
+    fn variance(&self) -> f64;
+
+    /// PMF on $0, 1, ..., \text{len} - 1$.
+    fn pmf(&self, len: usize) -> Pmf {
+        Pmf::new(0, (0..len).map(|x| self.prob(x)).collect())
+    }
+}
+
+/// Indicator of a success with probability `p`.
+#[derive(Debug, Clone, Copy)]
+pub struct Bernoulli {
+    pub p: f64,
+}
+
+impl Distribution for Bernoulli {
+    fn prob(&self, x: usize) -> f64 {
+        match x {
+            0 => 1.0 - self.p,
+            1 => self.p,
+            _ => 0.0,
+        }
+    }
+
+    fn mean(&self) -> f64 {
+        self.p
+    }
+
+    fn variance(&self) -> f64 {
+        self.p * (1.0 - self.p)
+    }
+}
+
+/// Number of successes in `n` trials.
+#[derive(Debug, Clone, Copy)]
+pub struct Binomial {
+    pub n: usize,
+    pub p: f64,
+}
+
+impl Distribution for Binomial {
+    fn prob(&self, x: usize) -> f64 {
+        if x > self.n {
+            return 0.0;
+        }
+        (ln_choose(self.n, x) + x_ln_y(x, self.p) + x_ln_y(self.n - x, 1.0 - self.p)).exp()
+    }
+
+    fn mean(&self) -> f64 {
+        self.n as f64 * self.p
+    }
+
+    fn variance(&self) -> f64 {
+        self.n as f64 * self.p * (1.0 - self.p)
+    }
+}
+
+/// Number of failures before the first success.
+#[derive(Debug, Clone, Copy)]
+pub struct Geometric {
+    pub p: f64,
+}
+
+impl Distribution for Geometric {
+    fn prob(&self, x: usize) -> f64 {
+        (x_ln_y(x, 1.0 - self.p) + self.p.ln()).exp()
+    }
+
+    fn mean(&self) -> f64 {
+        (1.0 - self.p) / self.p
+    }
+
+    fn variance(&self) -> f64 {
+        (1.0 - self.p) / (self.p * self.p)
+    }
+}
+
+/// Number of failures before the `r`-th success.
+#[derive(Debug, Clone, Copy)]
+pub struct NegativeBinomial {
+    pub r: usize,
+    pub p: f64,
+}
+
+impl Distribution for NegativeBinomial {
+    fn prob(&self, x: usize) -> f64 {
+        if self.r == 0 {
+            return if x == 0 { 1.0 } else { 0.0 };
+        }
+        (ln_choose(x + self.r - 1, x) + x_ln_y(self.r, self.p) + x_ln_y(x, 1.0 - self.p)).exp()
+    }
+
+    fn mean(&self) -> f64 {
+        self.r as f64 * (1.0 - self.p) / self.p
+    }
+
+    fn variance(&self) -> f64 {
+        self.r as f64 * (1.0 - self.p) / (self.p * self.p)
+    }
+}
+
+/// Number of successes in `n` draws without replacement from a population of `successes` and
+/// `failures`.
+///
+/// Drawing more than the population has probability zero everywhere.
+#[derive(Debug, Clone, Copy)]
+pub struct Hypergeometric {
+    pub successes: usize,
+    pub failures: usize,
+    pub n: usize,
+}
+
+impl Distribution for Hypergeometric {
+    fn prob(&self, x: usize) -> f64 {
+        if self.n > self.successes + self.failures
+            || x > self.successes
+            || x > self.n
+            || self.n - x > self.failures
+        {
+            return 0.0;
+        }
+        (ln_choose(self.successes, x) + ln_choose(self.failures, self.n - x)
+            - ln_choose(self.successes + self.failures, self.n))
+        .exp()
+    }
+
+    fn mean(&self) -> f64 {
+        self.n as f64 * self.successes as f64 / (self.successes + self.failures) as f64
+    }
+
+    fn variance(&self) -> f64 {
+        let total = (self.successes + self.failures) as f64;
+        let n = self.n as f64;
+        let p = self.successes as f64 / total;
+        n * p * (1.0 - p) * (total - n) / (total - 1.0)
+    }
+}
+
+/// Number of successes in `n` trials whose success probability is drawn from
+/// $\text{Beta}(\alpha, \beta)$.
+#[derive(Debug, Clone, Copy)]
+pub struct BetaBinomial {
+    pub n: usize,
+    pub alpha: f64,
+    pub beta: f64,
+}
+
+impl Distribution for BetaBinomial {
+    fn prob(&self, x: usize) -> f64 {
+        if x > self.n {
+            return 0.0;
+        }
+        let ln_beta = |a: f64, b: f64| ln_gamma(a) + ln_gamma(b) - ln_gamma(a + b);
+        let k = x as f64;
+        let n = self.n as f64;
+        (ln_choose(self.n, x) + ln_beta(k + self.alpha, n - k + self.beta)
+            - ln_beta(self.alpha, self.beta))
+        .exp()
+    }
+
+    fn mean(&self) -> f64 {
+        self.n as f64 * self.alpha / (self.alpha + self.beta)
+    }
+
+    fn variance(&self) -> f64 {
+        let (n, a, b) = (self.n as f64, self.alpha, self.beta);
+        n * a * b * (a + b + n) / ((a + b).powi(2) * (a + b + 1.0))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Poisson {
+    pub lambda: f64,
+}
+
+impl Distribution for Poisson {
+    fn prob(&self, x: usize) -> f64 {
+        (x_ln_y(x, self.lambda) - self.lambda - ln_factorial(x)).exp()
+    }
+
+    fn mean(&self) -> f64 {
+        self.lambda
+    }
+
+    fn variance(&self) -> f64 {
+        self.lambda
+    }
+}
+
+/// Probability that a gambler starting with `i` reaches `n` before going broke, winning each
+/// unit bet with probability `p`.
+pub fn gambler_s_ruin_win(i: usize, n: usize, p: f64) -> f64 {
+    assert!(i <= n);
+    if i == 0 {
+        return 0.0;
+    }
+    if i == n {
+        return 1.0;
+    }
+    let q = 1.0 - p;
+    if (p - q).abs() < 1e-12 {
+        return i as f64 / n as f64;
+    }
+    // $\frac{1 - (q/p)^i}{1 - (q/p)^n}$, rearranged to avoid overflow when $q > p$.
+    let r = q / p;
+    if r < 1.0 {
+        (1.0 - r.powi(i as i32)) / (1.0 - r.powi(n as i32))
+    } else {
+        let s = p / q;
+        s.powi((n - i) as i32) * (1.0 - s.powi(i as i32)) / (1.0 - s.powi(n as i32))
+    }
+}
+
+/// Probability of the category counts `x` in `x.iter().sum()` trials with category probabilities
+/// `p`.
+pub fn multinomial_prob(x: &[usize], p: &[f64]) -> f64 {
+    assert_eq!(x.len(), p.len());
+    let n = x.iter().sum();
+    let ln_p: f64 = x
+        .iter()
+        .zip(p)
+        .map(|(&x, &p)| x_ln_y(x, p) - ln_factorial(x))
+        .sum();
+    (ln_factorial(n) + ln_p).exp()
+}
+
+/// Probability of drawing the category counts `x` without replacement from a population with
+/// `population[i]` members of category `i`.
+pub fn multivariate_hypergeometric_prob(x: &[usize], population: &[usize]) -> f64 {
+    assert_eq!(x.len(), population.len());
+    if x.iter().zip(population).any(|(x, k)| x > k) {
+        return 0.0;
+    }
+    let ln_ways: f64 = x
+        .iter()
+        .zip(population)
+        .map(|(&x, &k)| ln_choose(k, x))
+        .sum();
+    (ln_ways - ln_choose(population.iter().sum(), x.iter().sum())).exp()
+}
+
+/// $\ln \Gamma(x)$ for $x > 0$ by the Lanczos approximation.
+pub fn ln_gamma(x: f64) -> f64 {
+    const G: f64 = 7.0;
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // Reflection formula.
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + G + 0.5;
+    let series = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, c)| {
+            sum + c / (x + i as f64 + 1.0)
+        });
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+/// $\ln n!$
+pub fn ln_factorial(n: usize) -> f64 {
+    ln_gamma(n as f64 + 1.0)
+}
+
+/// $\ln \binom{n}{k}$
+pub fn ln_choose(n: usize, k: usize) -> f64 {
+    assert!(k <= n);
+    ln_factorial(n) - ln_factorial(k) - ln_factorial(n - k)
+}
+
+/// $x \ln y$ with $0 \ln 0 = 0$.
+fn x_ln_y(x: usize, y: f64) -> f64 {
+    if x == 0 {
+        0.0
+    } else {
+        x as f64 * y.ln()
+    }
+}
@@ -0,0 +1,130 @@
+//! Posterior distributions of a random variable given observed evidence.
+//!
+//! A model generates its hidden state and the observations in the same run. Conditioning on the
+//! evidence keeps only the runs whose outcome agrees with what was observed.
+
+use std::hash::Hash;
+
+use crate::exact::exact_prob_mass_func;
+use crate::pmf::Pmf;
+use crate::stats::{wilson_interval, ConfidenceInterval};
+use crate::{sample, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition};
+
+/// Random variable that only tabulates outcomes consistent with the evidence.
+///
+/// Outcomes contradicting the evidence map outside the space, so that the counts of
+/// [`crate::sample_repeat`] and the PMF of [`exact_prob_mass_func`] are the joint
+/// distribution with the evidence.
+pub struct Given<RV, F> {
+    pub rv: RV,
+    pub evidence: F,
+}
+
+impl<RV, F> NonnegativeRandomVariable for Given<RV, F>
+where
+    RV: NonnegativeRandomVariable,
+    F: Fn(&RV::Outcome) -> bool,
+{
+    type Outcome = RV::Outcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        if (self.evidence)(&outcome) {
+            self.rv.map(outcome)
+        } else {
+            usize::MAX
+        }
+    }
+
+    fn space_len(&self) -> usize {
+        self.rv.space_len()
+    }
+}
+
+/// Posterior estimated by rejection sampling.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Posterior {
+    pub rounds: usize,
+    /// Number of rounds consistent with the evidence.
+    pub accepted: usize,
+    /// Number of accepted rounds in which the random variable took each value.
+    pub counts: Vec<usize>,
+}
+
+impl Posterior {
+    /// Estimate of the probability of the evidence.
+    pub fn evidence_probability(&self) -> f64 {
+        self.accepted as f64 / self.rounds as f64
+    }
+
+    /// Return `None` if no round was consistent with the evidence.
+    pub fn pmf(&self) -> Option<Pmf> {
+        if self.accepted == 0 {
+            return None;
+        }
+        Some(Pmf::from_mass(&self.counts, self.accepted))
+    }
+
+    /// Wilson intervals of the posterior probabilities, based on the accepted rounds.
+    ///
+    /// Return no intervals if no round was consistent with the evidence.
+    pub fn intervals(&self, confidence: f64) -> Vec<ConfidenceInterval> {
+        if self.accepted == 0 {
+            return Vec::new();
+        }
+        self.counts
+            .iter()
+            .map(|&count| wilson_interval(count, self.accepted, confidence))
+            .collect()
+    }
+}
+
+/// Sample `rounds` times and keep the rounds whose outcome is consistent with the evidence.
+pub fn posterior<S, RV, F, O>(start: S, rounds: usize, rv: RV, evidence: F) -> Posterior
+where
+    O: Outcome,
+    S: StartCondition<Outcome = O>,
+    RV: NonnegativeRandomVariable<Outcome = O>,
+    F: Fn(&O) -> bool,
+{
+    let mut counts = vec![0; rv.space_len()];
+    let mut accepted = 0;
+    for _ in 0..rounds {
+        let outcome = sample(start.build());
+        if !evidence(&outcome) {
+            continue;
+        }
+        accepted += 1;
+        let v = rv.map(outcome);
+        if v < counts.len() {
+            counts[v] += 1;
+        }
+    }
+    Posterior {
+        rounds,
+        accepted,
+        counts,
+    }
+}
+
+/// Compute the posterior without sampling.
+///
+/// The PMF is normalized over the values of the random variable. Return `None` if the evidence
+/// has probability zero.
+pub fn exact_posterior<S, RV, F, O>(start: S, rv: RV, evidence: F, tolerance: f64) -> Option<Pmf>
+where
+    O: Outcome,
+    S: StartCondition<Outcome = O>,
+    S::Condition: EventSpace + Clone + Eq + Hash,
+    RV: NonnegativeRandomVariable<Outcome = O>,
+    F: Fn(&O) -> bool,
+{
+    let joint = Pmf::from(exact_prob_mass_func(
+        start,
+        Given { rv, evidence },
+        tolerance,
+    ));
+    if joint.total() == 0.0 {
+        return None;
+    }
+    Some(joint.normalize())
+}
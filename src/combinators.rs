@@ -0,0 +1,189 @@
+//! Random variables built from closures and from other random variables.
+//!
+//! Values at or beyond the space of a random variable are not tabulated by
+//! [`crate::sample_repeat`], and the combinators keep them out of the space: if either operand
+//! of [`Sum`], [`Max`] or [`Min`] maps an outcome outside its space, so does the combination.
+
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
+
+use crate::{NonnegativeRandomVariable, Outcome};
+
+/// Random variable given by a closure on the outcome.
+pub struct Mapped<O, F> {
+    f: F,
+    space_len: usize,
+    outcome: PhantomData<fn(O)>,
+}
+
+impl<O, F> Mapped<O, F>
+where
+    O: Outcome,
+    F: Fn(O) -> usize,
+{
+    /// Random variable `f` taking values in $0, 1, ..., \text{space_len} - 1$.
+    pub fn new(space_len: usize, f: F) -> Self {
+        Self {
+            f,
+            space_len,
+            outcome: PhantomData,
+        }
+    }
+}
+
+impl<O, F> NonnegativeRandomVariable for Mapped<O, F>
+where
+    O: Outcome,
+    F: Fn(O) -> usize,
+{
+    type Outcome = O;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        (self.f)(outcome)
+    }
+
+    fn space_len(&self) -> usize {
+        self.space_len
+    }
+}
+
+/// Indicator of the outcome satisfying a predicate.
+pub struct Indicator<O, F> {
+    predicate: F,
+    outcome: PhantomData<fn(&O)>,
+}
+
+impl<O, F> Indicator<O, F>
+where
+    O: Outcome,
+    F: Fn(&O) -> bool,
+{
+    pub fn new(predicate: F) -> Self {
+        Self {
+            predicate,
+            outcome: PhantomData,
+        }
+    }
+}
+
+impl<O, F> NonnegativeRandomVariable for Indicator<O, F>
+where
+    O: Outcome,
+    F: Fn(&O) -> bool,
+{
+    type Outcome = O;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        (self.predicate)(&outcome) as usize
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+/// Values of both random variables on the same outcome, or `None` if either is outside its
+/// space.
+fn both<A, B>(a: &A, b: &B, outcome: A::Outcome) -> Option<(usize, usize)>
+where
+    A: NonnegativeRandomVariable,
+    A::Outcome: Clone,
+    B: NonnegativeRandomVariable<Outcome = A::Outcome>,
+{
+    let x = a.map(outcome.clone());
+    let y = b.map(outcome);
+    (x < a.space_len() && y < b.space_len()).then_some((x, y))
+}
+
+/// Sum of two random variables on the same outcome.
+pub struct Sum<A, B>(pub A, pub B);
+
+impl<A, B> NonnegativeRandomVariable for Sum<A, B>
+where
+    A: NonnegativeRandomVariable,
+    A::Outcome: Clone,
+    B: NonnegativeRandomVariable<Outcome = A::Outcome>,
+{
+    type Outcome = A::Outcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        both(&self.0, &self.1, outcome).map_or(usize::MAX, |(x, y)| x + y)
+    }
+
+    fn space_len(&self) -> usize {
+        (self.0.space_len() + self.1.space_len()).saturating_sub(1)
+    }
+}
+
+/// Larger of two random variables on the same outcome.
+pub struct Max<A, B>(pub A, pub B);
+
+impl<A, B> NonnegativeRandomVariable for Max<A, B>
+where
+    A: NonnegativeRandomVariable,
+    A::Outcome: Clone,
+    B: NonnegativeRandomVariable<Outcome = A::Outcome>,
+{
+    type Outcome = A::Outcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        both(&self.0, &self.1, outcome).map_or(usize::MAX, |(x, y)| x.max(y))
+    }
+
+    fn space_len(&self) -> usize {
+        self.0.space_len().max(self.1.space_len())
+    }
+}
+
+/// Smaller of two random variables on the same outcome.
+pub struct Min<A, B>(pub A, pub B);
+
+impl<A, B> NonnegativeRandomVariable for Min<A, B>
+where
+    A: NonnegativeRandomVariable,
+    A::Outcome: Clone,
+    B: NonnegativeRandomVariable<Outcome = A::Outcome>,
+{
+    type Outcome = A::Outcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        both(&self.0, &self.1, outcome).map_or(usize::MAX, |(x, y)| x.min(y))
+    }
+
+    fn space_len(&self) -> usize {
+        self.0.space_len().min(self.1.space_len())
+    }
+}
+
+/// Random variable forced into `support`: smaller values become its start and larger ones its
+/// end.
+///
+/// Values outside the space of the inner random variable stay outside, like in the other
+/// combinators, so the end only collects the part of the upper tail that the inner random
+/// variable tabulates. Give it a space beyond the end to collect the whole tail.
+pub struct Clamp<RV> {
+    rv: RV,
+    support: RangeInclusive<usize>,
+}
+
+impl<RV> Clamp<RV> {
+    pub fn new(rv: RV, support: RangeInclusive<usize>) -> Self {
+        assert!(support.start() <= support.end(), "empty support");
+        Self { rv, support }
+    }
+}
+
+impl<RV: NonnegativeRandomVariable> NonnegativeRandomVariable for Clamp<RV> {
+    type Outcome = RV::Outcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        match self.rv.map(outcome) {
+            x if x >= self.rv.space_len() => usize::MAX,
+            x => x.clamp(*self.support.start(), *self.support.end()),
+        }
+    }
+
+    fn space_len(&self) -> usize {
+        self.support.end() + 1
+    }
+}
@@ -0,0 +1,154 @@
+//! Continuous-time simulation by the Gillespie algorithm.
+//!
+//! Instead of probabilities, a continuous-time condition gives the rate of every event that may
+//! happen next. The time until the next event is exponential with the total rate, and each event
+//! is the next one with probability proportional to its rate.
+
+use std::marker::PhantomData;
+
+use rand::Rng;
+
+use crate::{rng, select, Event, NonnegativeRandomVariable, Outcome};
+
+/// Context that moves through states in continuous time until an outcome is reached.
+pub trait ContinuousCondition {
+    type Event: Event;
+    type Outcome: Outcome;
+
+    /// Return every event that may happen next together with its rate.
+    fn rates(&self) -> Vec<(f64, Self::Event)>;
+    /// Incorporate an event that happens at `time`.
+    fn push(&mut self, event: Self::Event, time: f64);
+    /// Return the outcome if the process stops at `time`.
+    fn outcome(&self, time: f64) -> Option<Self::Outcome>;
+    /// Time at which the process is stopped if no outcome was reached before.
+    ///
+    /// [`ContinuousCondition::outcome`] has to return an outcome at the horizon.
+    fn horizon(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+pub trait ContinuousStartCondition {
+    type Event: Event;
+    type Outcome: Outcome;
+    type Condition: ContinuousCondition<Event = Self::Event, Outcome = Self::Outcome>;
+
+    fn build(&self) -> Self::Condition;
+}
+
+/// Outcome together with the time it was reached.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timed<O> {
+    pub time: f64,
+    pub outcome: O,
+}
+
+impl<O: Outcome> Outcome for Timed<O> {}
+
+/// Draw from the exponential distribution with the given rate.
+pub fn exponential(rate: f64) -> f64 {
+    // Exclude zero so that the logarithm is finite.
+    let u: f64 = rng::with_rng(|rng| 1.0 - rng.gen::<f64>());
+    -u.ln() / rate
+}
+
+/// Run a continuous-time simulation until an outcome is reached and return the time with it.
+pub fn sample_continuous<C: ContinuousCondition>(start: C) -> (f64, C::Outcome) {
+    let mut cond = start;
+    let mut time = 0.0;
+    loop {
+        if let Some(outcome) = cond.outcome(time) {
+            return (time, outcome);
+        }
+
+        let rates = cond.rates();
+        let total: f64 = rates.iter().map(|(rate, _)| rate).sum();
+        assert!(
+            total > 0.0,
+            "no event can happen and no outcome was reached"
+        );
+        let next = time + exponential(total);
+        if next >= cond.horizon() {
+            time = cond.horizon();
+            let outcome = cond.outcome(time);
+            return (time, outcome.expect("no outcome at the horizon"));
+        }
+        time = next;
+        let space: Vec<(f64, usize)> = rates
+            .iter()
+            .enumerate()
+            .map(|(i, (rate, _))| (rate / total, i))
+            .collect();
+        let i = *select(&space);
+        let event = rates.into_iter().nth(i).unwrap().1;
+        cond.push(event, time);
+    }
+}
+
+/// Sample a random variable of the timed outcome a number of times and return the number of
+/// times each value was taken.
+pub fn sample_repeat_continuous<S, RV, O>(start: S, rounds: usize, rv: RV) -> Vec<usize>
+where
+    O: Outcome,
+    S: ContinuousStartCondition<Outcome = O>,
+    RV: NonnegativeRandomVariable<Outcome = Timed<O>>,
+{
+    let mut mass = vec![0; rv.space_len()];
+    for _ in 0..rounds {
+        let (time, outcome) = sample_continuous(start.build());
+        let v = rv.map(Timed { time, outcome });
+        if v >= mass.len() {
+            continue;
+        }
+        mass[v] += 1;
+    }
+    mass
+}
+
+/// Index of the bin of width `width` the time falls into.
+///
+/// Times beyond the last bin are not tabulated.
+pub struct TimeBinRandomVariable<O> {
+    pub width: f64,
+    pub bins: usize,
+    outcome: PhantomData<O>,
+}
+
+impl<O> TimeBinRandomVariable<O> {
+    pub fn new(width: f64, bins: usize) -> Self {
+        assert!(width > 0.0);
+        Self {
+            width,
+            bins,
+            outcome: PhantomData,
+        }
+    }
+}
+
+impl<O: Outcome> NonnegativeRandomVariable for TimeBinRandomVariable<O> {
+    type Outcome = Timed<O>;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        (outcome.time / self.width) as usize
+    }
+
+    fn space_len(&self) -> usize {
+        self.bins
+    }
+}
+
+/// Random variable of the outcome that ignores the time.
+pub struct Untimed<RV>(pub RV);
+
+impl<RV: NonnegativeRandomVariable> NonnegativeRandomVariable for Untimed<RV> {
+    type Outcome = Timed<RV::Outcome>;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        self.0.map(outcome.outcome)
+    }
+
+    fn space_len(&self) -> usize {
+        self.0.space_len()
+    }
+}
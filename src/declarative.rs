@@ -0,0 +1,391 @@
+//! Models described as data instead of Rust code.
+//!
+//! A model is a set of named states. Each state either leads to other states through weighted
+//! transitions or ends the run with a named outcome. The random variable to tabulate assigns a
+//! value to every outcome.
+//!
+//! ```toml
+//! name = "coin"
+//! start = "toss"
+//!
+//! [states.toss]
+//! transitions = [{ to = "heads", weight = 1 }, { to = "tails", weight = 1 }]
+//!
+//! [states.heads]
+//! outcome = "heads"
+//!
+//! [states.tails]
+//! outcome = "tails"
+//!
+//! [random_variable]
+//! heads = 1
+//! tails = 0
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+/// Serialized form of a model.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ModelSpec {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub name: Option<String>,
+    /// Name of the state every run starts from.
+    pub start: String,
+    pub states: BTreeMap<String, StateSpec>,
+    /// Value of the random variable for each outcome.
+    pub random_variable: BTreeMap<String, usize>,
+}
+
+/// Serialized form of a state.
+///
+/// Exactly one of `transitions` and `outcome` must be given.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StateSpec {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub transitions: Vec<TransitionSpec>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub outcome: Option<String>,
+}
+
+/// Serialized form of a transition.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TransitionSpec {
+    pub to: String,
+    /// Relative weight; the weights of a state are normalized to probabilities.
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModelError {
+    Parse(String),
+    UnknownStart(String),
+    UnknownState {
+        from: String,
+        to: String,
+    },
+    /// The state has neither transitions nor an outcome, or has both.
+    AmbiguousState(String),
+    InvalidWeight {
+        from: String,
+        to: String,
+    },
+    /// The transitions of the state all have zero weight.
+    ZeroWeight(String),
+    MissingValue(String),
+    /// No state with the outcome can be reached from the start state.
+    UnreachableOutcome(String),
+    /// The state can be reached from the start state, but no outcome can be reached from it.
+    NoOutcome(String),
+}
+
+impl fmt::Display for ModelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModelError::Parse(e) => write!(f, "failed to parse model: {e}"),
+            ModelError::UnknownStart(s) => write!(f, "start state `{s}` is not defined"),
+            ModelError::UnknownState { from, to } => {
+                write!(f, "state `{from}` transitions to undefined state `{to}`")
+            }
+            ModelError::AmbiguousState(s) => write!(
+                f,
+                "state `{s}` must have either transitions or an outcome, but not both"
+            ),
+            ModelError::InvalidWeight { from, to } => write!(
+                f,
+                "transition from `{from}` to `{to}` has a negative or non-finite weight"
+            ),
+            ModelError::ZeroWeight(s) => {
+                write!(f, "the transitions of state `{s}` all have zero weight")
+            }
+            ModelError::MissingValue(o) => {
+                write!(f, "outcome `{o}` has no value in the random variable")
+            }
+            ModelError::UnreachableOutcome(o) => {
+                write!(f, "outcome `{o}` cannot be reached from the start state")
+            }
+            ModelError::NoOutcome(s) => write!(f, "no outcome can be reached from state `{s}`"),
+        }
+    }
+}
+
+impl std::error::Error for ModelError {}
+
+#[derive(Debug, Clone)]
+enum State {
+    Transient(Vec<(f64, usize)>),
+    Terminal(usize),
+}
+
+/// Which states can be reached from `start` through transitions of positive weight.
+fn reachable(states: &[State], start: usize) -> Vec<bool> {
+    let mut reached = vec![false; states.len()];
+    reached[start] = true;
+    let mut stack = vec![start];
+    while let Some(state) = stack.pop() {
+        if let State::Transient(transitions) = &states[state] {
+            for &(p, to) in transitions {
+                if p > 0.0 && !reached[to] {
+                    reached[to] = true;
+                    stack.push(to);
+                }
+            }
+        }
+    }
+    reached
+}
+
+/// Which states can reach an outcome through transitions of positive weight.
+fn can_end(states: &[State]) -> Vec<bool> {
+    let mut ends: Vec<bool> = states
+        .iter()
+        .map(|s| matches!(s, State::Terminal(_)))
+        .collect();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (i, state) in states.iter().enumerate() {
+            if let State::Transient(transitions) = state {
+                if !ends[i] && transitions.iter().any(|&(p, to)| p > 0.0 && ends[to]) {
+                    ends[i] = true;
+                    changed = true;
+                }
+            }
+        }
+    }
+    ends
+}
+
+/// A validated model ready to be sampled.
+#[derive(Debug, Clone)]
+pub struct Model {
+    name: Option<String>,
+    state_names: Vec<String>,
+    states: Vec<State>,
+    outcome_names: Vec<String>,
+    /// Value of the random variable indexed by outcome.
+    values: Vec<usize>,
+    start: usize,
+}
+
+impl Model {
+    pub fn from_spec(spec: &ModelSpec) -> Result<Self, ModelError> {
+        let state_names: Vec<String> = spec.states.keys().cloned().collect();
+        let index_of = |name: &str| state_names.iter().position(|s| s == name);
+        let start =
+            index_of(&spec.start).ok_or_else(|| ModelError::UnknownStart(spec.start.clone()))?;
+
+        let mut outcome_names: Vec<String> = Vec::new();
+        let mut states = Vec::with_capacity(state_names.len());
+        for (name, state) in &spec.states {
+            match (&state.outcome, state.transitions.is_empty()) {
+                (Some(outcome), true) => {
+                    let i = match outcome_names.iter().position(|o| o == outcome) {
+                        Some(i) => i,
+                        None => {
+                            outcome_names.push(outcome.clone());
+                            outcome_names.len() - 1
+                        }
+                    };
+                    states.push(State::Terminal(i));
+                }
+                (None, false) => {
+                    let mut transitions = Vec::with_capacity(state.transitions.len());
+                    for t in &state.transitions {
+                        let to = index_of(&t.to).ok_or_else(|| ModelError::UnknownState {
+                            from: name.clone(),
+                            to: t.to.clone(),
+                        })?;
+                        if !t.weight.is_finite() || t.weight < 0.0 {
+                            return Err(ModelError::InvalidWeight {
+                                from: name.clone(),
+                                to: t.to.clone(),
+                            });
+                        }
+                        transitions.push((t.weight, to));
+                    }
+                    let total: f64 = transitions.iter().map(|(w, _)| w).sum();
+                    if total == 0.0 {
+                        return Err(ModelError::ZeroWeight(name.clone()));
+                    }
+                    transitions.iter_mut().for_each(|(w, _)| *w /= total);
+                    states.push(State::Transient(transitions));
+                }
+                _ => return Err(ModelError::AmbiguousState(name.clone())),
+            }
+        }
+
+        let reachable = reachable(&states, start);
+        for (i, outcome) in outcome_names.iter().enumerate() {
+            let reached = states
+                .iter()
+                .zip(&reachable)
+                .any(|(s, &r)| r && matches!(s, State::Terminal(o) if *o == i));
+            if !reached {
+                return Err(ModelError::UnreachableOutcome(outcome.clone()));
+            }
+        }
+        let can_end = can_end(&states);
+        if let Some(i) = (0..states.len()).find(|&i| reachable[i] && !can_end[i]) {
+            return Err(ModelError::NoOutcome(state_names[i].clone()));
+        }
+
+        let values = outcome_names
+            .iter()
+            .map(|o| {
+                spec.random_variable
+                    .get(o)
+                    .copied()
+                    .ok_or_else(|| ModelError::MissingValue(o.clone()))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            name: spec.name.clone(),
+            state_names,
+            states,
+            outcome_names,
+            values,
+            start,
+        })
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn from_toml_str(s: &str) -> Result<Self, ModelError> {
+        let spec: ModelSpec = toml::from_str(s).map_err(|e| ModelError::Parse(e.to_string()))?;
+        Self::from_spec(&spec)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn from_json_str(s: &str) -> Result<Self, ModelError> {
+        let spec: ModelSpec =
+            serde_json::from_str(s).map_err(|e| ModelError::Parse(e.to_string()))?;
+        Self::from_spec(&spec)
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn state_name(&self, state: usize) -> &str {
+        &self.state_names[state]
+    }
+
+    pub fn outcome_name(&self, outcome: &ModelOutcome) -> &str {
+        &self.outcome_names[outcome.0]
+    }
+
+    /// The random variable described by the model.
+    pub fn random_variable(&self) -> ModelRandomVariable<'_> {
+        ModelRandomVariable { model: self }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ModelEvent(usize);
+
+impl Event for ModelEvent {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelOutcome(usize);
+
+impl Outcome for ModelOutcome {}
+
+#[derive(Debug, Clone)]
+pub struct ModelCondition<'a> {
+    model: &'a Model,
+    /// Index of the current state.
+    state: usize,
+}
+
+impl ModelCondition<'_> {
+    pub fn state(&self) -> usize {
+        self.state
+    }
+}
+
+impl PartialEq for ModelCondition<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.model, other.model) && self.state == other.state
+    }
+}
+
+impl Eq for ModelCondition<'_> {}
+
+impl Hash for ModelCondition<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.state.hash(state);
+    }
+}
+
+impl Condition for ModelCondition<'_> {
+    type Event = ModelEvent;
+    type Outcome = ModelOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        self.state = event.0;
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        match &self.model.states[self.state] {
+            State::Terminal(o) => Some(ModelOutcome(*o)),
+            State::Transient(_) => None,
+        }
+    }
+}
+
+impl EventSpace for ModelCondition<'_> {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        match &self.model.states[self.state] {
+            State::Transient(transitions) => transitions
+                .iter()
+                .map(|&(p, to)| (p, ModelEvent(to)))
+                .collect(),
+            State::Terminal(_) => Vec::new(),
+        }
+    }
+}
+
+impl<'a> StartCondition for &'a Model {
+    type Event = ModelEvent;
+    type Outcome = ModelOutcome;
+    type Condition = ModelCondition<'a>;
+
+    fn build(&self) -> Self::Condition {
+        ModelCondition {
+            model: self,
+            state: self.start,
+        }
+    }
+}
+
+pub struct ModelRandomVariable<'a> {
+    model: &'a Model,
+}
+
+impl NonnegativeRandomVariable for ModelRandomVariable<'_> {
+    type Outcome = ModelOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        self.model.values[outcome.0]
+    }
+
+    fn space_len(&self) -> usize {
+        self.model.values.iter().max().map_or(0, |&v| v + 1)
+    }
+}
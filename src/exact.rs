@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{Condition, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition};
+
+/// Largest number of steps [`exact_prob_mass_func`] propagates the mass.
+pub const MAX_STEPS: usize = 1_000_000;
+
+/// Compute the probability mass function of a random variable without sampling.
+///
+/// The probability mass is pushed forward through the event spaces one step at a time. Equal
+/// conditions are merged, so models whose conditions only remember their current state stay
+/// small even when the number of paths explodes.
+///
+/// The propagation stops once the mass that has not reached an outcome drops to `tolerance`, or
+/// after [`MAX_STEPS`] steps if some of the mass never reaches an outcome.
+/// That mass, as well as the mass of values outside the space of the random variable, is not
+/// part of the returned function.
+pub fn exact_prob_mass_func<S, RV, O>(start: S, rv: RV, tolerance: f64) -> Vec<f64>
+where
+    O: Outcome,
+    S: StartCondition<Outcome = O>,
+    S::Condition: EventSpace + Clone + Eq + Hash,
+    RV: NonnegativeRandomVariable<Outcome = O>,
+{
+    let mut pmf = vec![0.0; rv.space_len()];
+    let mut frontier = HashMap::from([(start.build(), 1.0)]);
+    for _ in 0..MAX_STEPS {
+        if frontier.is_empty() || frontier.values().sum::<f64>() <= tolerance {
+            break;
+        }
+        let mut next = HashMap::with_capacity(frontier.len());
+        for (cond, mass) in frontier {
+            if let Some(outcome) = cond.outcome() {
+                let v = rv.map(outcome);
+                if v < pmf.len() {
+                    pmf[v] += mass;
+                }
+                continue;
+            }
+
+            for (p, event) in cond.event_space() {
+                if p == 0.0 {
+                    continue;
+                }
+                let mut cond = cond.clone();
+                cond.push(event);
+                *next.entry(cond).or_insert(0.0) += mass * p;
+            }
+        }
+        frontier = next;
+    }
+    pmf
+}
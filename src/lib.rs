@@ -1,5 +1,22 @@
 use rand::Rng;
 
+pub mod analytic;
+pub mod bayes;
+pub mod combinators;
+pub mod continuous;
+pub mod declarative;
+pub mod exact;
+pub mod mdp;
+pub mod models;
+pub mod pmf;
+pub mod policy;
+pub mod render;
+pub mod report;
+pub mod rng;
+pub mod sensitivity;
+pub mod stats;
+pub mod sweep;
+
 pub trait Event {}
 
 /// Context that walks through a sequence of choices until an outcome is reached.
@@ -15,6 +32,12 @@ pub trait Condition {
     fn outcome(&self) -> Option<Self::Outcome>;
 }
 
+/// Condition that can enumerate the events reachable from its current state.
+pub trait EventSpace: Condition {
+    /// Return every event that may happen next together with its probability.
+    fn event_space(&self) -> Vec<(f64, Self::Event)>;
+}
+
 pub trait StartCondition {
     type Event: Event;
     type Outcome: Outcome;
@@ -45,8 +68,7 @@ where
 
 /// Select an event from a space of events with given probabilities.
 pub fn select<E>(space: &[(f64, E)]) -> &E {
-    let mut rng = rand::thread_rng();
-    let r: f64 = rng.gen_range(0.0..1.0);
+    let r: f64 = rng::with_rng(|rng| rng.gen_range(0.0..1.0));
     let mut sum = 0.0;
     let mut event = None;
     for (p, e) in space {
@@ -56,7 +78,8 @@ pub fn select<E>(space: &[(f64, E)]) -> &E {
             break;
         }
     }
-    event.unwrap()
+    // Rounding may leave the cumulative sum just below one.
+    event.unwrap_or_else(|| &space.iter().rev().find(|(p, _)| *p > 0.0).unwrap().1)
 }
 
 pub trait NonnegativeRandomVariable {
@@ -0,0 +1,414 @@
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use cond_prob_sim::declarative::Model;
+use cond_prob_sim::models::bernoulli::{BernRandomVariable, BernStartCondition};
+use cond_prob_sim::models::binomial::{BinRandomVariable, BinStartCondition};
+use cond_prob_sim::models::gambler_s_ruin::{GRRandomVariable, GRStartCondition};
+use cond_prob_sim::models::geometric::{GeomRandomVariable, GeomStartCondition};
+use cond_prob_sim::models::hypergeometric::{HGeomRandomVariable, HGeomStartCondition};
+use cond_prob_sim::models::monty_hall::{
+    HostStrategy, MHGameRandomVariable, MHGameStartCondition, PlayerPolicy,
+};
+use cond_prob_sim::models::negative_binomial::{NBinRandomVariable, NBinStartCondition};
+use cond_prob_sim::render::{bar_chart, write_svg, Charset};
+use cond_prob_sim::report::SimulationReport;
+use cond_prob_sim::rng::seed;
+use cond_prob_sim::sample_repeat;
+
+/// Simulate a model and print the distribution of its random variable.
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    #[command(subcommand)]
+    model: ModelCommand,
+    /// Number of simulated rounds.
+    #[arg(short, long, global = true, default_value_t = 100_000)]
+    rounds: usize,
+    #[arg(short, long, global = true, value_enum, default_value_t = Format::Table)]
+    format: Format,
+    /// Confidence level of the intervals.
+    #[arg(long, global = true, default_value_t = 0.95, value_parser = confidence_level)]
+    confidence: f64,
+    /// Seed of the random number generator; a random one is picked if omitted.
+    #[arg(long, global = true)]
+    seed: Option<u64>,
+    /// Also draw the PMF with its confidence intervals to an SVG file.
+    #[arg(long, global = true)]
+    svg: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum ModelCommand {
+    /// Indicator of a single success.
+    Bernoulli {
+        #[arg(long, value_parser = probability)]
+        p: f64,
+    },
+    /// Number of successes in `n` trials.
+    Binomial {
+        #[arg(long)]
+        n: usize,
+        #[arg(long, value_parser = probability)]
+        p: f64,
+    },
+    /// Number of failures before the first success.
+    Geometric {
+        #[arg(long, value_parser = success_probability)]
+        p: f64,
+        /// Largest number of failures tabulated; the rest are counted as untabulated.
+        #[arg(long, default_value_t = 20)]
+        max: usize,
+    },
+    /// Number of failures before the `r`-th success.
+    NegativeBinomial {
+        #[arg(long)]
+        r: usize,
+        #[arg(long, value_parser = success_probability)]
+        p: f64,
+        /// Largest number of failures tabulated; the rest are counted as untabulated.
+        #[arg(long, default_value_t = 100)]
+        max: usize,
+    },
+    /// Number of successes in `n` draws without replacement.
+    Hypergeometric {
+        /// Number of successes in the population.
+        #[arg(long)]
+        successes: usize,
+        /// Number of failures in the population.
+        #[arg(long)]
+        failures: usize,
+        #[arg(long)]
+        n: usize,
+    },
+    /// Indicator of A reaching `n` starting with `i`.
+    GamblersRuin {
+        #[arg(long)]
+        i: usize,
+        #[arg(long)]
+        n: usize,
+        #[arg(long, value_parser = probability)]
+        p: f64,
+    },
+    /// Indicator of winning the car; games where the host reveals it are not tabulated.
+    MontyHall {
+        #[arg(long, default_value_t = 3)]
+        doors: usize,
+        /// Number of doors the host opens.
+        #[arg(long, default_value_t = 1)]
+        opened: usize,
+        #[arg(long, value_enum, default_value_t = Host::Knows)]
+        host: Host,
+        /// Probability that a biased host opens the lowest-numbered eligible door.
+        #[arg(long, default_value_t = 1.0, value_parser = probability)]
+        bias: f64,
+        #[arg(long, value_enum, default_value_t = Policy::Switch)]
+        policy: Policy,
+    },
+    /// Model described in a TOML or JSON file.
+    File { path: PathBuf },
+}
+
+/// Parse a probability in $[0, 1]$.
+fn probability(s: &str) -> Result<f64, String> {
+    let p: f64 = s.parse().map_err(|e| format!("{e}"))?;
+    if !(0.0..=1.0).contains(&p) {
+        return Err(format!("{p} is not a probability in [0, 1]"));
+    }
+    Ok(p)
+}
+
+/// Parse a success probability in $(0, 1]$, so that a success eventually happens.
+fn success_probability(s: &str) -> Result<f64, String> {
+    let p = probability(s)?;
+    if p == 0.0 {
+        return Err("the success probability must be positive".into());
+    }
+    Ok(p)
+}
+
+/// Parse a confidence level in $(0, 1)$.
+fn confidence_level(s: &str) -> Result<f64, String> {
+    let level: f64 = s.parse().map_err(|e| format!("{e}"))?;
+    if !(level > 0.0 && level < 1.0) {
+        return Err(format!("{level} is not a confidence level in (0, 1)"));
+    }
+    Ok(level)
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Host {
+    Knows,
+    Ignorant,
+    Biased,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Policy {
+    Stay,
+    Switch,
+    Random,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Table,
+    /// Bar chart drawn with Unicode block elements.
+    Chart,
+    Csv,
+    Json,
+}
+
+struct Run {
+    model: String,
+    parameters: Vec<(&'static str, f64)>,
+    mass: Vec<usize>,
+}
+
+fn run(model: ModelCommand, rounds: usize) -> Result<Run, String> {
+    let run = match model {
+        ModelCommand::Bernoulli { p } => Run {
+            model: "bernoulli".into(),
+            parameters: vec![("p", p)],
+            mass: sample_repeat(BernStartCondition { p }, rounds, BernRandomVariable),
+        },
+        ModelCommand::Binomial { n, p } => Run {
+            model: "binomial".into(),
+            parameters: vec![("n", n as f64), ("p", p)],
+            mass: sample_repeat(BinStartCondition { n, p }, rounds, BinRandomVariable { n }),
+        },
+        ModelCommand::Geometric { p, max } => Run {
+            model: "geometric".into(),
+            parameters: vec![("p", p)],
+            mass: sample_repeat(
+                GeomStartCondition { p },
+                rounds,
+                GeomRandomVariable { n: max },
+            ),
+        },
+        ModelCommand::NegativeBinomial { r, p, max } => Run {
+            model: "negative_binomial".into(),
+            parameters: vec![("r", r as f64), ("p", p)],
+            mass: sample_repeat(
+                NBinStartCondition { r, p },
+                rounds,
+                NBinRandomVariable { n: max },
+            ),
+        },
+        ModelCommand::Hypergeometric {
+            successes,
+            failures,
+            n,
+        } => {
+            if n > successes + failures {
+                return Err("cannot draw more than the population without replacement".into());
+            }
+            Run {
+                model: "hypergeometric".into(),
+                parameters: vec![
+                    ("successes", successes as f64),
+                    ("failures", failures as f64),
+                    ("n", n as f64),
+                ],
+                mass: sample_repeat(
+                    HGeomStartCondition {
+                        successes_remaining: successes,
+                        failures_remaining: failures,
+                        n,
+                    },
+                    rounds,
+                    HGeomRandomVariable { n },
+                ),
+            }
+        }
+        ModelCommand::GamblersRuin { i, n, p } => {
+            if i > n {
+                return Err("A cannot start with more than the target".into());
+            }
+            Run {
+                model: "gambler_s_ruin".into(),
+                parameters: vec![("i", i as f64), ("n", n as f64), ("p", p)],
+                mass: sample_repeat(GRStartCondition { i, n, p }, rounds, GRRandomVariable),
+            }
+        }
+        ModelCommand::MontyHall {
+            doors,
+            opened,
+            host,
+            bias,
+            policy,
+        } => {
+            if opened + 2 > doors {
+                return Err("the host must leave at least two doors closed".into());
+            }
+            let (host, host_name) = match host {
+                Host::Knows => (HostStrategy::Knows, "knowing"),
+                Host::Ignorant => (HostStrategy::Ignorant, "ignorant"),
+                Host::Biased => (HostStrategy::Biased { p: bias }, "biased"),
+            };
+            let (policy, policy_name) = match policy {
+                Policy::Stay => (PlayerPolicy::Stay, "stay"),
+                Policy::Switch => (PlayerPolicy::Switch, "switch"),
+                Policy::Random => (PlayerPolicy::Random, "random"),
+            };
+            let mut parameters = vec![("doors", doors as f64), ("opened", opened as f64)];
+            if let HostStrategy::Biased { p } = host {
+                parameters.push(("bias", p));
+            }
+            let start = MHGameStartCondition {
+                doors,
+                opened,
+                host,
+                policy,
+            };
+            Run {
+                model: format!("monty_hall ({host_name} host, {policy_name})"),
+                parameters,
+                mass: sample_repeat(start, rounds, MHGameRandomVariable),
+            }
+        }
+        ModelCommand::File { path } => {
+            let text = std::fs::read_to_string(&path)
+                .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+            let model = match path.extension().and_then(|e| e.to_str()) {
+                Some("json") => Model::from_json_str(&text),
+                _ => Model::from_toml_str(&text),
+            }
+            .map_err(|e| e.to_string())?;
+            let name = match model.name() {
+                Some(name) => name.to_string(),
+                None => path.display().to_string(),
+            };
+            Run {
+                model: name,
+                parameters: vec![],
+                mass: sample_repeat(&model, rounds, model.random_variable()),
+            }
+        }
+    };
+    Ok(run)
+}
+
+fn print(report: &SimulationReport, format: Format) -> io::Result<()> {
+    let stdout = io::stdout().lock();
+    match format {
+        Format::Table => print_table(report, stdout),
+        Format::Chart => {
+            let mut stdout = stdout;
+            write!(
+                stdout,
+                "{}",
+                bar_chart(&report.pmf, None, 60, Charset::Unicode)
+            )
+        }
+        Format::Csv => report.write_csv(stdout),
+        Format::Json => {
+            report.write_json(stdout)?;
+            println!();
+            Ok(())
+        }
+    }
+}
+
+fn print_table<W: Write>(report: &SimulationReport, mut w: W) -> io::Result<()> {
+    writeln!(w, "model: {}", report.model)?;
+    for (name, value) in &report.parameters {
+        writeln!(w, "{name} = {value}")?;
+    }
+    if let Some(seed) = report.seed {
+        writeln!(w, "seed: {seed}")?;
+    }
+    writeln!(w, "rounds: {}", report.rounds)?;
+    if report.untabulated > 0 {
+        writeln!(w, "untabulated: {}", report.untabulated)?;
+    }
+    writeln!(w)?;
+    let level = report.confidence * 100.0;
+    writeln!(
+        w,
+        "{:>8} {:>10} {:>12} {:>26}",
+        "value",
+        "count",
+        "probability",
+        format!("{level}% CI")
+    )?;
+    let rows = report
+        .counts
+        .iter()
+        .zip(&report.pmf)
+        .zip(&report.pmf_intervals);
+    for (x, ((count, p), ci)) in rows.enumerate() {
+        writeln!(
+            w,
+            "{:>8} {:>10} {:>12.6} {:>26}",
+            x,
+            count,
+            p,
+            format!("[{:.6}, {:.6}]", ci.lower, ci.upper)
+        )?;
+    }
+    if let (Some(mean), Some(ci), Some(variance)) =
+        (report.mean, report.mean_interval, report.variance)
+    {
+        writeln!(w)?;
+        writeln!(
+            w,
+            "mean: {:.6} ({level}% CI [{:.6}, {:.6}])",
+            mean, ci.lower, ci.upper
+        )?;
+        writeln!(w, "variance: {:.6}", variance)?;
+    } else {
+        writeln!(w)?;
+        writeln!(
+            w,
+            "mean and variance omitted: {} rounds fell outside the table",
+            report.untabulated
+        )?;
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if cli.rounds == 0 {
+        eprintln!("error: --rounds must be positive");
+        return ExitCode::FAILURE;
+    }
+    let seed_value = cli.seed.unwrap_or_else(rand::random);
+    seed(seed_value);
+    let run = match run(cli.model, cli.rounds) {
+        Ok(run) => run,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut report = SimulationReport::new(run.model, run.mass, cli.rounds, cli.confidence);
+    report.parameters = run
+        .parameters
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+    report.seed = Some(seed_value);
+    if let Err(e) = print(&report, cli.format) {
+        eprintln!("error: {e}");
+        return ExitCode::FAILURE;
+    }
+    if let Some(path) = cli.svg {
+        let written = std::fs::File::create(&path).and_then(|file| {
+            write_svg(
+                io::BufWriter::new(file),
+                &report.pmf,
+                None,
+                Some(&report.pmf_intervals),
+            )
+        });
+        if let Err(e) = written {
+            eprintln!("error: failed to write {}: {e}", path.display());
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
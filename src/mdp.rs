@@ -0,0 +1,269 @@
+//! Optimal policies for conditions with choice points.
+//!
+//! The conditions reachable from a start condition form a Markov decision process: chance picks
+//! the next event by the event space, and the decision maker picks it among the choices. The
+//! reward is collected when an outcome is reached.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::policy::{deterministic, DecisionCondition, Policy};
+use crate::EventSpace;
+
+#[derive(Debug, Clone)]
+enum Node {
+    Terminal(f64),
+    /// Probability and index of each successor.
+    Chance(Vec<(f64, usize)>),
+    /// Index of the successor of each choice.
+    Decision(Vec<usize>),
+}
+
+/// Markov decision process of all conditions reachable from a start condition.
+#[derive(Debug, Clone)]
+pub struct Mdp<C> {
+    index: HashMap<C, usize>,
+    nodes: Vec<Node>,
+}
+
+/// Optimal values of the states and choices that attain them.
+#[derive(Debug, Clone)]
+pub struct Solution<'a, C> {
+    mdp: &'a Mdp<C>,
+    values: Vec<f64>,
+    /// Chosen index at each decision node.
+    choices: Vec<Option<usize>>,
+}
+
+impl<C> Mdp<C>
+where
+    C: DecisionCondition + EventSpace + Clone + Eq + Hash,
+{
+    /// Enumerate the conditions reachable from `start`, where reaching an outcome earns
+    /// `reward(outcome)`.
+    ///
+    /// Equal conditions are merged, so the process is only finite if the conditions only
+    /// remember a bounded state.
+    pub fn explore(start: C, reward: impl Fn(C::Outcome) -> f64) -> Self {
+        let mut index = HashMap::from([(start.clone(), 0)]);
+        let mut nodes = vec![Node::Terminal(0.0)];
+        let mut queue = VecDeque::from([start]);
+        let mut id = |cond: C, nodes: &mut Vec<Node>, queue: &mut VecDeque<C>| {
+            *index.entry(cond.clone()).or_insert_with(|| {
+                nodes.push(Node::Terminal(0.0));
+                queue.push_back(cond);
+                nodes.len() - 1
+            })
+        };
+        let mut i = 0;
+        while let Some(cond) = queue.pop_front() {
+            let node = if let Some(outcome) = cond.outcome() {
+                Node::Terminal(reward(outcome))
+            } else if let Some(choices) = cond.choices() {
+                assert!(!choices.is_empty());
+                let next = choices
+                    .into_iter()
+                    .map(|event| {
+                        let mut next = cond.clone();
+                        next.push(event);
+                        id(next, &mut nodes, &mut queue)
+                    })
+                    .collect();
+                Node::Decision(next)
+            } else {
+                let next = cond
+                    .event_space()
+                    .into_iter()
+                    .filter(|(p, _)| *p > 0.0)
+                    .map(|(p, event)| {
+                        let mut next = cond.clone();
+                        next.push(event);
+                        (p, id(next, &mut nodes, &mut queue))
+                    })
+                    .collect();
+                Node::Chance(next)
+            };
+            nodes[i] = node;
+            i += 1;
+        }
+        Self { index, nodes }
+    }
+
+    /// Number of reachable conditions.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Solve by value iteration, sweeping until no value changes by more than `tolerance`.
+    pub fn value_iteration(&self, tolerance: f64) -> Solution<'_, C> {
+        let mut values = vec![0.0; self.len()];
+        loop {
+            let mut change: f64 = 0.0;
+            // Successors are mostly discovered after their predecessors.
+            for i in (0..self.len()).rev() {
+                let value = match &self.nodes[i] {
+                    Node::Terminal(reward) => *reward,
+                    Node::Chance(next) => next.iter().map(|&(p, j)| p * values[j]).sum(),
+                    Node::Decision(next) => next
+                        .iter()
+                        .map(|&j| values[j])
+                        .fold(f64::NEG_INFINITY, f64::max),
+                };
+                change = change.max((value - values[i]).abs());
+                values[i] = value;
+            }
+            if change <= tolerance {
+                break;
+            }
+        }
+        let choices = self.greedy_choices(&values, None, tolerance);
+        Solution {
+            mdp: self,
+            values,
+            choices,
+        }
+    }
+
+    /// Solve by policy iteration, starting with the first choice everywhere and evaluating each
+    /// policy to within `tolerance`.
+    pub fn policy_iteration(&self, tolerance: f64) -> Solution<'_, C> {
+        let mut choices: Vec<Option<usize>> = self
+            .nodes
+            .iter()
+            .map(|node| match node {
+                Node::Decision(_) => Some(0),
+                _ => None,
+            })
+            .collect();
+        loop {
+            let values = self.evaluate_choices(&choices, tolerance);
+            let improved = self.greedy_choices(&values, Some(&choices), tolerance);
+            if improved == choices {
+                return Solution {
+                    mdp: self,
+                    values,
+                    choices,
+                };
+            }
+            choices = improved;
+        }
+    }
+
+    /// Expected reward from the start condition when following `policy`.
+    pub fn evaluate<P: Policy<C>>(&self, policy: &P, tolerance: f64) -> f64 {
+        let mut conds: Vec<Option<&C>> = vec![None; self.len()];
+        for (cond, &i) in &self.index {
+            conds[i] = Some(cond);
+        }
+        let probs: Vec<Option<Vec<f64>>> = conds
+            .iter()
+            .zip(&self.nodes)
+            .map(|(cond, node)| match node {
+                Node::Decision(_) => {
+                    let cond = cond.unwrap();
+                    Some(policy.probabilities(cond, &cond.choices().unwrap()))
+                }
+                _ => None,
+            })
+            .collect();
+        self.evaluate_with(tolerance, |i, values| {
+            let Node::Decision(next) = &self.nodes[i] else {
+                unreachable!()
+            };
+            let probs = probs[i].as_ref().unwrap();
+            next.iter().zip(probs).map(|(&j, p)| p * values[j]).sum()
+        })[0]
+    }
+
+    fn evaluate_choices(&self, choices: &[Option<usize>], tolerance: f64) -> Vec<f64> {
+        self.evaluate_with(tolerance, |i, values| {
+            let Node::Decision(next) = &self.nodes[i] else {
+                unreachable!()
+            };
+            values[next[choices[i].unwrap()]]
+        })
+    }
+
+    /// Iterate the values with `decide` giving the value of a decision node.
+    fn evaluate_with(&self, tolerance: f64, decide: impl Fn(usize, &[f64]) -> f64) -> Vec<f64> {
+        let mut values = vec![0.0; self.len()];
+        loop {
+            let mut change: f64 = 0.0;
+            for i in (0..self.len()).rev() {
+                let value = match &self.nodes[i] {
+                    Node::Terminal(reward) => *reward,
+                    Node::Chance(next) => next.iter().map(|&(p, j)| p * values[j]).sum(),
+                    Node::Decision(_) => decide(i, &values),
+                };
+                change = change.max((value - values[i]).abs());
+                values[i] = value;
+            }
+            if change <= tolerance {
+                return values;
+            }
+        }
+    }
+
+    /// Pick the best choice at each decision node, keeping the current one unless another is
+    /// better by more than `tolerance`.
+    fn greedy_choices(
+        &self,
+        values: &[f64],
+        current: Option<&[Option<usize>]>,
+        tolerance: f64,
+    ) -> Vec<Option<usize>> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| {
+                let Node::Decision(next) = node else {
+                    return None;
+                };
+                let mut best = current.map_or(0, |c| c[i].unwrap());
+                for (k, &j) in next.iter().enumerate() {
+                    if values[j] > values[next[best]] + tolerance {
+                        best = k;
+                    }
+                }
+                Some(best)
+            })
+            .collect()
+    }
+}
+
+impl<C> Solution<'_, C>
+where
+    C: Eq + Hash,
+{
+    /// Optimal expected reward from the start condition.
+    pub fn value(&self) -> f64 {
+        self.values[0]
+    }
+
+    /// Optimal expected reward from a reachable condition.
+    pub fn value_of(&self, cond: &C) -> Option<f64> {
+        self.mdp.index.get(cond).map(|&i| self.values[i])
+    }
+
+    /// Index of the optimal choice at a reachable choice point.
+    pub fn choice(&self, cond: &C) -> Option<usize> {
+        self.mdp.index.get(cond).and_then(|&i| self.choices[i])
+    }
+}
+
+/// The optimal policy. Panics at conditions that are not part of the process.
+impl<C> Policy<C> for &Solution<'_, C>
+where
+    C: DecisionCondition + Eq + Hash,
+{
+    fn probabilities(&self, cond: &C, choices: &[C::Event]) -> Vec<f64> {
+        let choice = self
+            .choice(cond)
+            .expect("condition is not a reachable choice point");
+        deterministic(choice, choices.len())
+    }
+}
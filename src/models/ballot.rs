@@ -0,0 +1,152 @@
+//! Bertrand's ballot problem: the ballots of an election are counted in random order.
+
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+/// Candidate on the counted ballot.
+#[derive(Debug, Clone)]
+pub enum BallotEvent {
+    A,
+    B,
+}
+
+impl Event for BallotEvent {}
+
+pub struct BallotOutcome {
+    /// Whether A was strictly ahead after every ballot.
+    pub always_ahead: bool,
+    /// Whether A was never behind.
+    pub never_behind: bool,
+}
+
+impl Outcome for BallotOutcome {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BallotCondition {
+    /// Ballots for A and B that have not been counted yet.
+    remaining_a: usize,
+    remaining_b: usize,
+    /// Votes for A minus votes for B so far.
+    lead: i64,
+    always_ahead: bool,
+    never_behind: bool,
+}
+
+impl BallotCondition {
+    pub fn new(a: usize, b: usize) -> Self {
+        assert!(a + b > 0);
+        Self {
+            remaining_a: a,
+            remaining_b: b,
+            lead: 0,
+            always_ahead: true,
+            never_behind: true,
+        }
+    }
+}
+
+impl Condition for BallotCondition {
+    type Event = BallotEvent;
+    type Outcome = BallotOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        match event {
+            BallotEvent::A => {
+                self.remaining_a -= 1;
+                self.lead += 1;
+            }
+            BallotEvent::B => {
+                self.remaining_b -= 1;
+                self.lead -= 1;
+            }
+        }
+        self.always_ahead &= self.lead > 0;
+        self.never_behind &= self.lead >= 0;
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        if self.remaining_a + self.remaining_b == 0 {
+            Some(BallotOutcome {
+                always_ahead: self.always_ahead,
+                never_behind: self.never_behind,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl EventSpace for BallotCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        let p = self.remaining_a as f64 / (self.remaining_a + self.remaining_b) as f64;
+        vec![(p, BallotEvent::A), (1. - p, BallotEvent::B)]
+    }
+}
+
+pub struct BallotStartCondition {
+    /// Votes for A.
+    pub a: usize,
+    /// Votes for B.
+    pub b: usize,
+}
+
+impl StartCondition for BallotStartCondition {
+    type Event = BallotEvent;
+    type Outcome = BallotOutcome;
+    type Condition = BallotCondition;
+
+    fn build(&self) -> Self::Condition {
+        BallotCondition::new(self.a, self.b)
+    }
+}
+
+/// Indicator of A being strictly ahead throughout the count.
+pub struct BallotRandomVariable;
+
+impl NonnegativeRandomVariable for BallotRandomVariable {
+    type Outcome = BallotOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.always_ahead as usize
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+/// Indicator of A never being behind during the count.
+pub struct BallotNeverBehindRandomVariable;
+
+impl NonnegativeRandomVariable for BallotNeverBehindRandomVariable {
+    type Outcome = BallotOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.never_behind as usize
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+/// Probability that A is strictly ahead throughout the count, $\frac{a - b}{a + b}$.
+pub fn ahead_probability(a: usize, b: usize) -> f64 {
+    if a <= b {
+        return 0.;
+    }
+    (a - b) as f64 / (a + b) as f64
+}
+
+/// Probability that A is never behind during the count, $\frac{a + 1 - b}{a + 1}$.
+pub fn never_behind_probability(a: usize, b: usize) -> f64 {
+    if a < b {
+        return 0.;
+    }
+    (a + 1 - b) as f64 / (a + 1) as f64
+}
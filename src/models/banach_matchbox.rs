@@ -0,0 +1,118 @@
+//! Banach's matchbox problem: a smoker takes matches from one of two boxes of `n` matches,
+//! chosen at random, until reaching for an empty box.
+
+use crate::analytic::ln_choose;
+use crate::pmf::Pmf;
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+/// Box the smoker reaches for.
+#[derive(Debug, Clone)]
+pub enum BanachEvent {
+    Left,
+    Right,
+}
+
+impl Event for BanachEvent {}
+
+pub struct BanachOutcome {
+    /// Matches left in the other box when one is found empty.
+    pub remaining: usize,
+}
+
+impl Outcome for BanachOutcome {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BanachCondition {
+    left: usize,
+    right: usize,
+    /// Matches in the other box once an empty box has been found.
+    remaining: Option<usize>,
+}
+
+impl BanachCondition {
+    pub fn new(n: usize) -> Self {
+        Self {
+            left: n,
+            right: n,
+            remaining: None,
+        }
+    }
+}
+
+impl Condition for BanachCondition {
+    type Event = BanachEvent;
+    type Outcome = BanachOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        assert!(self.remaining.is_none());
+        let (chosen, other) = match event {
+            BanachEvent::Left => (&mut self.left, self.right),
+            BanachEvent::Right => (&mut self.right, self.left),
+        };
+        if *chosen == 0 {
+            self.remaining = Some(other);
+        } else {
+            *chosen -= 1;
+        }
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        self.remaining.map(|remaining| BanachOutcome { remaining })
+    }
+}
+
+impl EventSpace for BanachCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        vec![(0.5, BanachEvent::Left), (0.5, BanachEvent::Right)]
+    }
+}
+
+pub struct BanachStartCondition {
+    /// Matches per box.
+    pub n: usize,
+}
+
+impl StartCondition for BanachStartCondition {
+    type Event = BanachEvent;
+    type Outcome = BanachOutcome;
+    type Condition = BanachCondition;
+
+    fn build(&self) -> Self::Condition {
+        BanachCondition::new(self.n)
+    }
+}
+
+pub struct BanachRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for BanachRandomVariable {
+    type Outcome = BanachOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.remaining
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// Distribution of the matches left in the other box.
+///
+/// $P(K = k) = \binom{2n - k}{n} 2^{k - 2n}$
+pub fn remaining_pmf(n: usize) -> Pmf {
+    let probs = (0..=n)
+        .map(|k| {
+            let m = 2 * n - k;
+            (ln_choose(m, n) - m as f64 * std::f64::consts::LN_2).exp()
+        })
+        .collect();
+    Pmf::new(0, probs)
+}
@@ -0,0 +1,139 @@
+//! Bertrand's box paradox: a coin is drawn from a box chosen at random. Given the color of the
+//! drawn coin, what is the color of the other coin in the box?
+
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Coin {
+    Gold,
+    Silver,
+}
+
+#[derive(Debug, Clone)]
+pub enum BertrandEvent {
+    /// Index of the chosen box.
+    Box(usize),
+    /// Index of the coin drawn from the box.
+    Coin(usize),
+}
+
+impl Event for BertrandEvent {}
+
+pub struct BertrandOutcome {
+    pub drawn: Coin,
+    pub other: Coin,
+}
+
+impl Outcome for BertrandOutcome {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BertrandCondition {
+    boxes: Vec<[Coin; 2]>,
+    chosen: Option<usize>,
+    drawn: Option<usize>,
+}
+
+impl BertrandCondition {
+    pub fn new(boxes: Vec<[Coin; 2]>) -> Self {
+        assert!(!boxes.is_empty());
+        Self {
+            boxes,
+            chosen: None,
+            drawn: None,
+        }
+    }
+}
+
+impl Condition for BertrandCondition {
+    type Event = BertrandEvent;
+    type Outcome = BertrandOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        match event {
+            BertrandEvent::Box(i) => self.chosen = Some(i),
+            BertrandEvent::Coin(i) => self.drawn = Some(i),
+        }
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        let coins = self.boxes[self.chosen?];
+        let drawn = self.drawn?;
+        Some(BertrandOutcome {
+            drawn: coins[drawn],
+            other: coins[1 - drawn],
+        })
+    }
+}
+
+impl EventSpace for BertrandCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        match self.chosen {
+            None => {
+                let p = 1. / self.boxes.len() as f64;
+                (0..self.boxes.len())
+                    .map(|i| (p, BertrandEvent::Box(i)))
+                    .collect()
+            }
+            Some(_) => vec![(0.5, BertrandEvent::Coin(0)), (0.5, BertrandEvent::Coin(1))],
+        }
+    }
+}
+
+pub struct BertrandStartCondition {
+    pub boxes: Vec<[Coin; 2]>,
+}
+
+impl BertrandStartCondition {
+    /// One box with two gold coins, one with two silver coins and one with one of each.
+    pub fn classic() -> Self {
+        Self {
+            boxes: vec![
+                [Coin::Gold, Coin::Gold],
+                [Coin::Silver, Coin::Silver],
+                [Coin::Gold, Coin::Silver],
+            ],
+        }
+    }
+}
+
+impl StartCondition for BertrandStartCondition {
+    type Event = BertrandEvent;
+    type Outcome = BertrandOutcome;
+    type Condition = BertrandCondition;
+
+    fn build(&self) -> Self::Condition {
+        BertrandCondition::new(self.boxes.clone())
+    }
+}
+
+/// Indicator of the other coin being gold.
+pub struct BertrandOtherGoldRandomVariable;
+
+impl NonnegativeRandomVariable for BertrandOtherGoldRandomVariable {
+    type Outcome = BertrandOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        (outcome.other == Coin::Gold) as usize
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+/// Probability that the other coin is gold given that a gold coin was drawn.
+///
+/// Each gold coin is equally likely to be the drawn one, so this is the share of gold coins whose
+/// partner is gold; $2 / 3$ for the classic boxes.
+pub fn other_gold_probability(boxes: &[[Coin; 2]]) -> f64 {
+    let gold = |coins: &[Coin; 2]| coins.iter().filter(|&&c| c == Coin::Gold).count();
+    let total: usize = boxes.iter().map(gold).sum();
+    let paired: usize = boxes.iter().filter(|coins| gold(coins) == 2).count() * 2;
+    paired as f64 / total as f64
+}
@@ -0,0 +1,104 @@
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BinEvent {
+    Success,
+    Failure,
+}
+
+impl Event for BinEvent {}
+
+pub struct BinOutcome {
+    pub successes: usize,
+    pub failures: usize,
+}
+
+impl Outcome for BinOutcome {}
+
+pub struct BinCondition {
+    successes: usize,
+    failures: usize,
+    /// Probability of success.
+    p: f64,
+    /// Number of trials.
+    n: usize,
+}
+
+impl BinCondition {
+    pub fn new(n: usize, p: f64) -> Self {
+        Self {
+            successes: 0,
+            failures: 0,
+            p,
+            n,
+        }
+    }
+}
+
+impl Condition for BinCondition {
+    type Event = BinEvent;
+    type Outcome = BinOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        match &event {
+            BinEvent::Success => self.successes += 1,
+            BinEvent::Failure => self.failures += 1,
+        }
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        assert!(self.successes + self.failures <= self.n);
+        if self.successes + self.failures == self.n {
+            Some(BinOutcome {
+                successes: self.successes,
+                failures: self.failures,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl EventSpace for BinCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        let q = 1. - self.p;
+        vec![(self.p, BinEvent::Success), (q, BinEvent::Failure)]
+    }
+}
+
+pub struct BinStartCondition {
+    pub n: usize,
+    pub p: f64,
+}
+
+impl StartCondition for BinStartCondition {
+    type Event = BinEvent;
+    type Outcome = BinOutcome;
+    type Condition = BinCondition;
+
+    fn build(&self) -> Self::Condition {
+        BinCondition::new(self.n, self.p)
+    }
+}
+
+pub struct BinRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for BinRandomVariable {
+    type Outcome = BinOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.successes
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
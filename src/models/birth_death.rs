@@ -0,0 +1,167 @@
+//! Linear birth–death process: every individual independently gives birth at rate `birth` and
+//! dies at rate `death`.
+
+use crate::continuous::{ContinuousCondition, ContinuousStartCondition};
+use crate::{Event, NonnegativeRandomVariable, Outcome};
+
+#[derive(Debug, Clone)]
+pub enum BDEvent {
+    Birth,
+    Death,
+}
+
+impl Event for BDEvent {}
+
+#[derive(Debug, Clone)]
+pub struct BDOutcome {
+    pub population: usize,
+    pub extinct: bool,
+}
+
+impl Outcome for BDOutcome {}
+
+#[derive(Debug, Clone)]
+pub struct BDCondition {
+    population: usize,
+    birth: f64,
+    death: f64,
+    max_population: usize,
+    horizon: f64,
+}
+
+impl BDCondition {
+    pub fn new(
+        initial: usize,
+        birth: f64,
+        death: f64,
+        max_population: usize,
+        horizon: f64,
+    ) -> Self {
+        Self {
+            population: initial,
+            birth,
+            death,
+            max_population,
+            horizon,
+        }
+    }
+}
+
+impl ContinuousCondition for BDCondition {
+    type Event = BDEvent;
+    type Outcome = BDOutcome;
+
+    fn rates(&self) -> Vec<(f64, Self::Event)> {
+        let n = self.population as f64;
+        vec![
+            (n * self.birth, BDEvent::Birth),
+            (n * self.death, BDEvent::Death),
+        ]
+    }
+
+    fn push(&mut self, event: Self::Event, _time: f64) {
+        match event {
+            BDEvent::Birth => self.population += 1,
+            BDEvent::Death => self.population -= 1,
+        }
+    }
+
+    fn outcome(&self, time: f64) -> Option<Self::Outcome> {
+        if self.population == 0 || self.population >= self.max_population || time >= self.horizon {
+            Some(BDOutcome {
+                population: self.population,
+                extinct: self.population == 0,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn horizon(&self) -> f64 {
+        self.horizon
+    }
+}
+
+pub struct BDStartCondition {
+    pub initial: usize,
+    /// Birth rate per individual.
+    pub birth: f64,
+    /// Death rate per individual.
+    pub death: f64,
+    /// Population from which a run is stopped, since it almost surely never dies out.
+    pub max_population: usize,
+    pub horizon: f64,
+}
+
+impl ContinuousStartCondition for BDStartCondition {
+    type Event = BDEvent;
+    type Outcome = BDOutcome;
+    type Condition = BDCondition;
+
+    fn build(&self) -> Self::Condition {
+        BDCondition::new(
+            self.initial,
+            self.birth,
+            self.death,
+            self.max_population,
+            self.horizon,
+        )
+    }
+}
+
+/// Indicator of extinction.
+pub struct BDExtinctionRandomVariable;
+
+impl NonnegativeRandomVariable for BDExtinctionRandomVariable {
+    type Outcome = BDOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.extinct as usize
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+/// Population when the run stopped.
+pub struct BDPopulationRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for BDPopulationRandomVariable {
+    type Outcome = BDOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.population
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// Probability that the descendants of `initial` individuals die out by time `t`.
+///
+/// $\left(\frac{\mu (e^{(\lambda - \mu) t} - 1)}{\lambda e^{(\lambda - \mu) t} - \mu}\right)^{n_0}$,
+/// or $\left(\frac{\lambda t}{1 + \lambda t}\right)^{n_0}$ for $\lambda = \mu$.
+pub fn extinction_probability_by(initial: usize, birth: f64, death: f64, t: f64) -> f64 {
+    let one = if (birth - death).abs() < 1e-12 {
+        birth * t / (1. + birth * t)
+    } else {
+        let growth = ((birth - death) * t).exp();
+        death * (growth - 1.) / (birth * growth - death)
+    };
+    one.powi(initial as i32)
+}
+
+/// Probability that the descendants of `initial` individuals eventually die out,
+/// $\min(1, \mu / \lambda)^{n_0}$.
+pub fn extinction_probability(initial: usize, birth: f64, death: f64) -> f64 {
+    (death / birth).min(1.).powi(initial as i32)
+}
+
+/// Expected population at time `t`, $n_0 e^{(\lambda - \mu) t}$.
+pub fn expected_population(initial: usize, birth: f64, death: f64, t: f64) -> f64 {
+    initial as f64 * ((birth - death) * t).exp()
+}
@@ -0,0 +1,158 @@
+//! Birthday problem: people with uniformly random birthdays enter a room one at a time.
+
+use crate::pmf::Pmf;
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+/// Birthday of the person entering the room.
+#[derive(Debug, Clone)]
+pub struct BirthdayEvent(pub usize);
+
+impl Event for BirthdayEvent {}
+
+pub struct BirthdayOutcome {
+    /// Whether two people share a birthday.
+    pub collision: bool,
+    /// Number of people in the room, which is the first one sharing a birthday on a collision.
+    pub people: usize,
+}
+
+impl Outcome for BirthdayOutcome {}
+
+#[derive(Debug, Clone)]
+pub struct BirthdayCondition {
+    seen: Vec<bool>,
+    people: usize,
+    collision: bool,
+    /// Number of people to stop at when nobody shares a birthday.
+    max_people: usize,
+}
+
+impl BirthdayCondition {
+    pub fn new(days: usize, max_people: usize) -> Self {
+        assert!(days > 0);
+        Self {
+            seen: vec![false; days],
+            people: 0,
+            collision: false,
+            max_people,
+        }
+    }
+}
+
+impl Condition for BirthdayCondition {
+    type Event = BirthdayEvent;
+    type Outcome = BirthdayOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        assert!(!self.collision);
+        self.people += 1;
+        if self.seen[event.0] {
+            self.collision = true;
+        }
+        self.seen[event.0] = true;
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        if self.collision || self.people == self.max_people {
+            Some(BirthdayOutcome {
+                collision: self.collision,
+                people: self.people,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl EventSpace for BirthdayCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        let days = self.seen.len();
+        (0..days)
+            .map(|d| (1. / days as f64, BirthdayEvent(d)))
+            .collect()
+    }
+}
+
+pub struct BirthdayStartCondition {
+    pub days: usize,
+    /// Number of people in the room. A collision is certain with more people than days.
+    pub people: usize,
+}
+
+impl StartCondition for BirthdayStartCondition {
+    type Event = BirthdayEvent;
+    type Outcome = BirthdayOutcome;
+    type Condition = BirthdayCondition;
+
+    fn build(&self) -> Self::Condition {
+        BirthdayCondition::new(self.days, self.people)
+    }
+}
+
+/// Indicator of a shared birthday.
+pub struct BirthdayCollisionRandomVariable;
+
+impl NonnegativeRandomVariable for BirthdayCollisionRandomVariable {
+    type Outcome = BirthdayOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.collision as usize
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+/// Number of people until the first shared birthday.
+///
+/// Runs without a collision are not tabulated.
+pub struct BirthdayFirstCollisionRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for BirthdayFirstCollisionRandomVariable {
+    type Outcome = BirthdayOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        if outcome.collision {
+            outcome.people
+        } else {
+            usize::MAX
+        }
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// Probability that two of `people` share a birthday.
+///
+/// $1 - \prod_{k=0}^{\text{people} - 1} (1 - k / \text{days})$
+pub fn collision_probability(days: usize, people: usize) -> f64 {
+    let distinct: f64 = (0..people)
+        .map(|k| (1. - k as f64 / days as f64).max(0.))
+        .product();
+    1. - distinct
+}
+
+/// Distribution of the number of people until the first shared birthday.
+pub fn first_collision_pmf(days: usize) -> Pmf {
+    let probs = (0..=days + 1)
+        .map(|k| {
+            if k < 2 {
+                0.
+            } else {
+                collision_probability(days, k) - collision_probability(days, k - 1)
+            }
+        })
+        .collect();
+    Pmf::new(0, probs)
+}
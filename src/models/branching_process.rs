@@ -0,0 +1,227 @@
+//! Galton–Watson branching process.
+//!
+//! Every individual of a generation independently has a number of children drawn from the
+//! offspring distribution; the children form the next generation.
+
+use crate::pmf::Pmf;
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+/// Number of children of one individual.
+#[derive(Debug, Clone)]
+pub struct GWEvent(pub usize);
+
+impl Event for GWEvent {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GWFate {
+    Extinct,
+    /// Still alive at the horizon or past the population cap.
+    Survived,
+}
+
+#[derive(Debug, Clone)]
+pub struct GWOutcome {
+    pub fate: GWFate,
+    /// Generation in which the population died out or the run was stopped.
+    pub generations: usize,
+    /// Number of individuals ever alive, including the initial population.
+    pub total_progeny: usize,
+    /// Size of the last generation.
+    pub population: usize,
+}
+
+impl Outcome for GWOutcome {}
+
+#[derive(Debug, Clone)]
+pub struct GWCondition {
+    space: Vec<(f64, GWEvent)>,
+    horizon: usize,
+    max_population: usize,
+    generation: usize,
+    /// Individuals of the current generation that have not reproduced yet.
+    remaining: usize,
+    /// Size of the current generation.
+    population: usize,
+    /// Children born into the next generation so far.
+    children: usize,
+    total_progeny: usize,
+}
+
+impl GWCondition {
+    pub fn new(offspring: &Pmf, initial: usize, horizon: usize, max_population: usize) -> Self {
+        Self {
+            space: offspring.values().map(|(k, p)| (p, GWEvent(k))).collect(),
+            horizon,
+            max_population,
+            generation: 0,
+            remaining: initial,
+            population: initial,
+            children: 0,
+            total_progeny: initial,
+        }
+    }
+}
+
+impl Condition for GWCondition {
+    type Event = GWEvent;
+    type Outcome = GWOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        assert!(self.remaining > 0);
+        self.remaining -= 1;
+        self.children += event.0;
+        self.total_progeny += event.0;
+        if self.remaining == 0 {
+            self.generation += 1;
+            self.population = self.children;
+            self.remaining = self.children;
+            self.children = 0;
+        }
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.space).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        // Only decide between generations.
+        if self.remaining != self.population {
+            return None;
+        }
+        let fate = if self.population == 0 {
+            GWFate::Extinct
+        } else if self.generation >= self.horizon || self.population >= self.max_population {
+            GWFate::Survived
+        } else {
+            return None;
+        };
+        Some(GWOutcome {
+            fate,
+            generations: self.generation,
+            total_progeny: self.total_progeny,
+            population: self.population,
+        })
+    }
+}
+
+impl EventSpace for GWCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        self.space.clone()
+    }
+}
+
+pub struct GWStartCondition {
+    /// Distribution of the number of children of an individual.
+    pub offspring: Pmf,
+    pub initial: usize,
+    /// Number of generations after which a living population counts as surviving.
+    pub horizon: usize,
+    /// Population from which a run is stopped and counted as surviving, since it almost surely
+    /// never dies out.
+    pub max_population: usize,
+}
+
+impl StartCondition for GWStartCondition {
+    type Event = GWEvent;
+    type Outcome = GWOutcome;
+    type Condition = GWCondition;
+
+    fn build(&self) -> Self::Condition {
+        GWCondition::new(
+            &self.offspring,
+            self.initial,
+            self.horizon,
+            self.max_population,
+        )
+    }
+}
+
+/// Indicator of extinction.
+pub struct GWExtinctionRandomVariable;
+
+impl NonnegativeRandomVariable for GWExtinctionRandomVariable {
+    type Outcome = GWOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        match outcome.fate {
+            GWFate::Extinct => 1,
+            GWFate::Survived => 0,
+        }
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+/// Generation in which the population dies out.
+///
+/// Surviving runs are not tabulated.
+pub struct GWExtinctionTimeRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for GWExtinctionTimeRandomVariable {
+    type Outcome = GWOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        match outcome.fate {
+            GWFate::Extinct => outcome.generations,
+            GWFate::Survived => usize::MAX,
+        }
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// Number of individuals ever alive in runs that die out.
+///
+/// Surviving runs are not tabulated.
+pub struct GWTotalProgenyRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for GWTotalProgenyRandomVariable {
+    type Outcome = GWOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        match outcome.fate {
+            GWFate::Extinct => outcome.total_progeny,
+            GWFate::Survived => usize::MAX,
+        }
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// Probability that the descendants of `initial` individuals have died out by `generations`.
+///
+/// $G^{(n)}(0)^{\text{initial}}$ where $G$ is the PGF of the offspring distribution.
+pub fn extinction_probability_by(offspring: &Pmf, initial: usize, generations: usize) -> f64 {
+    let q = (0..generations).fold(0.0, |q, _| offspring.pgf(q));
+    q.powi(initial as i32)
+}
+
+/// Probability that the descendants of `initial` individuals eventually die out.
+///
+/// The smallest fixed point of the offspring PGF in $[0, 1]$, found by iterating the PGF from
+/// zero. Convergence is slow in the critical case, where the result is only accurate to about
+/// $10^{-6}$.
+pub fn extinction_probability(offspring: &Pmf, initial: usize) -> f64 {
+    let mut q = 0.0;
+    for _ in 0..1_000_000 {
+        let next = offspring.pgf(q);
+        if (next - q).abs() < 1e-15 {
+            q = next;
+            break;
+        }
+        q = next;
+    }
+    q.powi(initial as i32)
+}
@@ -0,0 +1,363 @@
+//! Decks of playing cards drawn without replacement, and games played with them.
+
+use std::collections::BTreeMap;
+
+use crate::pmf::Pmf;
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Card {
+    /// 1 for an ace, 11 to 13 for jack, queen and king.
+    pub rank: u8,
+    pub suit: Suit,
+}
+
+impl Card {
+    /// Value in blackjack, counting an ace as one.
+    pub fn blackjack_value(&self) -> usize {
+        (self.rank as usize).min(10)
+    }
+}
+
+/// Card drawn from a deck.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CardEvent(pub Card);
+
+impl Event for CardEvent {}
+
+/// Multiset of cards that have not been drawn yet.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Deck {
+    /// Number of copies of each remaining card, ordered by card; cards with no copies left are
+    /// removed.
+    counts: Vec<(Card, usize)>,
+}
+
+impl Deck {
+    pub fn from_cards(cards: impl IntoIterator<Item = Card>) -> Self {
+        let mut counts = BTreeMap::new();
+        for card in cards {
+            *counts.entry(card).or_insert(0) += 1;
+        }
+        Self {
+            counts: counts.into_iter().collect(),
+        }
+    }
+
+    /// The 52 cards of a French deck.
+    pub fn standard() -> Self {
+        Self::shoe(1)
+    }
+
+    /// Several standard decks shuffled together.
+    pub fn shoe(decks: usize) -> Self {
+        let deck = Suit::ALL
+            .iter()
+            .flat_map(|&suit| (1..=13).map(move |rank| Card { rank, suit }));
+        Self {
+            counts: Self::from_cards(deck)
+                .counts
+                .into_iter()
+                .map(|(card, _)| (card, decks))
+                .filter(|&(_, count)| count > 0)
+                .collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.counts.iter().map(|(_, count)| count).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of copies of `card` left.
+    pub fn count(&self, card: &Card) -> usize {
+        self.counts
+            .iter()
+            .find(|(c, _)| c == card)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    /// Each remaining card with the probability of drawing it next.
+    pub fn event_space(&self) -> Vec<(f64, CardEvent)> {
+        let len = self.len() as f64;
+        self.counts
+            .iter()
+            .map(|&(card, count)| (count as f64 / len, CardEvent(card)))
+            .collect()
+    }
+
+    pub fn draw(&self) -> CardEvent {
+        *select(&self.event_space())
+    }
+
+    /// Take a copy of `card` out of the deck.
+    pub fn remove(&mut self, card: &Card) {
+        let i = self
+            .counts
+            .iter()
+            .position(|(c, _)| c == card)
+            .expect("card is not in the deck");
+        self.counts[i].1 -= 1;
+        if self.counts[i].1 == 0 {
+            self.counts.remove(i);
+        }
+    }
+}
+
+pub struct DrawOutcome {
+    /// Cards in the order they were drawn.
+    pub hand: Vec<Card>,
+}
+
+impl Outcome for DrawOutcome {}
+
+/// Draw `n` cards without replacement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DrawCondition {
+    deck: Deck,
+    hand: Vec<Card>,
+    n: usize,
+}
+
+impl DrawCondition {
+    pub fn new(deck: Deck, n: usize) -> Self {
+        assert!(n <= deck.len());
+        Self {
+            deck,
+            hand: Vec::with_capacity(n),
+            n,
+        }
+    }
+}
+
+impl Condition for DrawCondition {
+    type Event = CardEvent;
+    type Outcome = DrawOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        assert!(self.hand.len() < self.n);
+        self.deck.remove(&event.0);
+        self.hand.push(event.0);
+    }
+
+    fn select_event(&self) -> Self::Event {
+        self.deck.draw()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        if self.hand.len() == self.n {
+            Some(DrawOutcome {
+                hand: self.hand.clone(),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl EventSpace for DrawCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        self.deck.event_space()
+    }
+}
+
+pub struct DrawStartCondition {
+    pub deck: Deck,
+    pub n: usize,
+}
+
+impl StartCondition for DrawStartCondition {
+    type Event = CardEvent;
+    type Outcome = DrawOutcome;
+    type Condition = DrawCondition;
+
+    fn build(&self) -> Self::Condition {
+        DrawCondition::new(self.deck.clone(), self.n)
+    }
+}
+
+/// Number of cards in the hand for which `matches` holds.
+pub struct CardCountRandomVariable<F> {
+    pub matches: F,
+    pub n: usize,
+}
+
+impl<F> NonnegativeRandomVariable for CardCountRandomVariable<F>
+where
+    F: Fn(&Card) -> bool,
+{
+    type Outcome = DrawOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome
+            .hand
+            .iter()
+            .filter(|card| (self.matches)(card))
+            .count()
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+pub struct DealerOutcome {
+    /// Best total of the final hand; above 21 on a bust.
+    pub total: usize,
+    pub cards: usize,
+}
+
+impl Outcome for DealerOutcome {}
+
+/// Blackjack dealer drawing to 17, or until the deck runs out.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DealerCondition {
+    deck: Deck,
+    /// Total counting every ace as one.
+    hard_total: usize,
+    has_ace: bool,
+    cards: usize,
+    /// Whether the dealer hits a soft 17 instead of standing.
+    hit_soft_17: bool,
+}
+
+impl DealerCondition {
+    pub fn new(deck: Deck, hit_soft_17: bool) -> Self {
+        Self {
+            deck,
+            hard_total: 0,
+            has_ace: false,
+            cards: 0,
+            hit_soft_17,
+        }
+    }
+}
+
+impl Condition for DealerCondition {
+    type Event = CardEvent;
+    type Outcome = DealerOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        self.deck.remove(&event.0);
+        self.hard_total += event.0.blackjack_value();
+        self.has_ace |= event.0.rank == 1;
+        self.cards += 1;
+    }
+
+    fn select_event(&self) -> Self::Event {
+        self.deck.draw()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        let (total, soft) = best_total(self.hard_total, self.has_ace);
+        let stands =
+            total > 17 || (total == 17 && !(soft && self.hit_soft_17)) || self.deck.is_empty();
+        if stands {
+            Some(DealerOutcome {
+                total,
+                cards: self.cards,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl EventSpace for DealerCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        self.deck.event_space()
+    }
+}
+
+pub struct DealerStartCondition {
+    pub deck: Deck,
+    /// Face-up card the dealer already holds, if any. It is taken out of the deck.
+    pub upcard: Option<Card>,
+    pub hit_soft_17: bool,
+}
+
+impl StartCondition for DealerStartCondition {
+    type Event = CardEvent;
+    type Outcome = DealerOutcome;
+    type Condition = DealerCondition;
+
+    fn build(&self) -> Self::Condition {
+        let mut cond = DealerCondition::new(self.deck.clone(), self.hit_soft_17);
+        if let Some(upcard) = self.upcard {
+            cond.push(CardEvent(upcard));
+        }
+        cond
+    }
+}
+
+/// Final total of the dealer, with every bust counted as 22.
+pub struct DealerTotalRandomVariable;
+
+impl NonnegativeRandomVariable for DealerTotalRandomVariable {
+    type Outcome = DealerOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.total.min(22)
+    }
+
+    fn space_len(&self) -> usize {
+        23
+    }
+}
+
+/// Distribution of the dealer's final total, with every bust counted as 22, when cards are drawn
+/// with replacement as from an infinite shoe.
+pub fn infinite_shoe_dealer_pmf(upcard: Option<u8>, hit_soft_17: bool) -> Pmf {
+    // Totals only grow, so the mass of each hard total is final once the smaller ones have been
+    // passed on. `mass[hard_total][has_ace]`
+    let mut mass = vec![[0.; 2]; 32];
+    match upcard {
+        Some(rank) => mass[(rank as usize).min(10)][(rank == 1) as usize] = 1.,
+        None => mass[0][0] = 1.,
+    }
+    let mut probs = vec![0.; 23];
+    for hard_total in 0..mass.len() {
+        for has_ace in [false, true] {
+            let m = mass[hard_total][has_ace as usize];
+            if m == 0. {
+                continue;
+            }
+            let (total, soft) = best_total(hard_total, has_ace);
+            if total > 17 || (total == 17 && !(soft && hit_soft_17)) {
+                probs[total.min(22)] += m;
+                continue;
+            }
+            for rank in 1..=13 {
+                let value = (rank as usize).min(10);
+                mass[hard_total + value][(has_ace || rank == 1) as usize] += m / 13.;
+            }
+        }
+    }
+    Pmf::new(0, probs)
+}
+
+/// Best blackjack total of a hand and whether it counts an ace as eleven.
+fn best_total(hard_total: usize, has_ace: bool) -> (usize, bool) {
+    if has_ace && hard_total + 10 <= 21 {
+        (hard_total + 10, true)
+    } else {
+        (hard_total, false)
+    }
+}
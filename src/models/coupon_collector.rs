@@ -0,0 +1,140 @@
+//! Coupon collector's problem: draw uniformly among `n` coupon types until all have been seen.
+
+use crate::pmf::Pmf;
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+/// Type of the drawn coupon.
+#[derive(Debug, Clone)]
+pub struct CCEvent(pub usize);
+
+impl Event for CCEvent {}
+
+pub struct CCOutcome {
+    /// Number of draws needed to complete the collection.
+    pub draws: usize,
+}
+
+impl Outcome for CCOutcome {}
+
+#[derive(Debug, Clone)]
+pub struct CCCondition {
+    collected: Vec<bool>,
+    distinct: usize,
+    draws: usize,
+}
+
+impl CCCondition {
+    /// With no coupon types, the collection is complete before the first draw.
+    pub fn new(n: usize) -> Self {
+        Self {
+            collected: vec![false; n],
+            distinct: 0,
+            draws: 0,
+        }
+    }
+}
+
+impl Condition for CCCondition {
+    type Event = CCEvent;
+    type Outcome = CCOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        self.draws += 1;
+        if !self.collected[event.0] {
+            self.collected[event.0] = true;
+            self.distinct += 1;
+        }
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        if self.distinct == self.collected.len() {
+            Some(CCOutcome { draws: self.draws })
+        } else {
+            None
+        }
+    }
+}
+
+impl EventSpace for CCCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        let n = self.collected.len();
+        (0..n).map(|i| (1. / n as f64, CCEvent(i))).collect()
+    }
+}
+
+pub struct CCStartCondition {
+    /// Number of coupon types.
+    pub n: usize,
+}
+
+impl StartCondition for CCStartCondition {
+    type Event = CCEvent;
+    type Outcome = CCOutcome;
+    type Condition = CCCondition;
+
+    fn build(&self) -> Self::Condition {
+        CCCondition::new(self.n)
+    }
+}
+
+pub struct CCRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for CCRandomVariable {
+    type Outcome = CCOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.draws
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// $n H_n$
+pub fn expected_draws(n: usize) -> f64 {
+    (1..=n).map(|k| n as f64 / k as f64).sum()
+}
+
+/// $n^2 \sum_{k=1}^n \frac{1}{k^2} - n H_n$
+pub fn draws_variance(n: usize) -> f64 {
+    let n_f = n as f64;
+    (1..=n).map(|k| n_f * n_f / (k * k) as f64).sum::<f64>() - expected_draws(n)
+}
+
+/// Distribution of the number of draws on $0, 1, ..., \text{len} - 1$.
+///
+/// Propagates the number of distinct coupons seen, which avoids the cancellation of the
+/// inclusion–exclusion formula.
+pub fn draws_pmf(n: usize, len: usize) -> Pmf {
+    if n == 0 {
+        // Nothing to collect.
+        return Pmf::new(0, (0..len).map(|x| if x == 0 { 1. } else { 0. }).collect());
+    }
+    // `distinct[k]` is the probability of having seen `k` types and not all of them.
+    let mut distinct = vec![0.; n];
+    distinct[0] = 1.;
+    let mut probs = vec![0.; len];
+    for p in probs.iter_mut().skip(1) {
+        let mut next = vec![0.; n];
+        for (k, &mass) in distinct.iter().enumerate() {
+            let new = (n - k) as f64 / n as f64;
+            next[k] += mass * (1. - new);
+            if k + 1 == n {
+                *p += mass * new;
+            } else {
+                next[k + 1] += mass * new;
+            }
+        }
+        distinct = next;
+    }
+    Pmf::new(0, probs)
+}
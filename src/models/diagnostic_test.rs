@@ -0,0 +1,160 @@
+//! Diagnostic test of a condition with a given prevalence, possibly repeated.
+
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+#[derive(Debug, Clone)]
+pub enum DiagnosticEvent {
+    /// Hidden state of the patient.
+    Sick(bool),
+    /// Observed result of one test.
+    Positive(bool),
+}
+
+impl Event for DiagnosticEvent {}
+
+pub struct DiagnosticOutcome {
+    pub sick: bool,
+    /// Number of positive results.
+    pub positives: usize,
+    pub tests: usize,
+}
+
+impl Outcome for DiagnosticOutcome {}
+
+#[derive(Debug, Clone)]
+pub struct DiagnosticCondition {
+    sick: Option<bool>,
+    positives: usize,
+    done: usize,
+    /// Number of independent tests.
+    tests: usize,
+    prevalence: f64,
+    sensitivity: f64,
+    specificity: f64,
+}
+
+impl DiagnosticCondition {
+    pub fn new(prevalence: f64, sensitivity: f64, specificity: f64, tests: usize) -> Self {
+        Self {
+            sick: None,
+            positives: 0,
+            done: 0,
+            tests,
+            prevalence,
+            sensitivity,
+            specificity,
+        }
+    }
+}
+
+state_eq!(DiagnosticCondition, sick, positives, done);
+
+impl Condition for DiagnosticCondition {
+    type Event = DiagnosticEvent;
+    type Outcome = DiagnosticOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        match event {
+            DiagnosticEvent::Sick(sick) => {
+                assert!(self.sick.is_none());
+                self.sick = Some(sick);
+            }
+            DiagnosticEvent::Positive(positive) => {
+                assert!(self.sick.is_some() && self.done < self.tests);
+                self.done += 1;
+                self.positives += positive as usize;
+            }
+        }
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        match self.sick {
+            Some(sick) if self.done == self.tests => Some(DiagnosticOutcome {
+                sick,
+                positives: self.positives,
+                tests: self.tests,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl EventSpace for DiagnosticCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        let p = match self.sick {
+            None => {
+                let p = self.prevalence;
+                return vec![
+                    (p, DiagnosticEvent::Sick(true)),
+                    (1. - p, DiagnosticEvent::Sick(false)),
+                ];
+            }
+            Some(true) => self.sensitivity,
+            Some(false) => 1. - self.specificity,
+        };
+        vec![
+            (p, DiagnosticEvent::Positive(true)),
+            (1. - p, DiagnosticEvent::Positive(false)),
+        ]
+    }
+}
+
+pub struct DiagnosticStartCondition {
+    /// Probability of being sick before testing.
+    pub prevalence: f64,
+    /// Probability of a positive result when sick.
+    pub sensitivity: f64,
+    /// Probability of a negative result when healthy.
+    pub specificity: f64,
+    pub tests: usize,
+}
+
+impl StartCondition for DiagnosticStartCondition {
+    type Event = DiagnosticEvent;
+    type Outcome = DiagnosticOutcome;
+    type Condition = DiagnosticCondition;
+
+    fn build(&self) -> Self::Condition {
+        DiagnosticCondition::new(
+            self.prevalence,
+            self.sensitivity,
+            self.specificity,
+            self.tests,
+        )
+    }
+}
+
+/// Indicator of being sick.
+pub struct DiagnosticSickRandomVariable;
+
+impl NonnegativeRandomVariable for DiagnosticSickRandomVariable {
+    type Outcome = DiagnosticOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.sick as usize
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+/// Probability of being sick given `positives` positive results out of `tests`, by Bayes' rule.
+pub fn posterior_sick(
+    prevalence: f64,
+    sensitivity: f64,
+    specificity: f64,
+    positives: usize,
+    tests: usize,
+) -> f64 {
+    let likelihood = |p: f64| p.powi(positives as i32) * (1. - p).powi((tests - positives) as i32);
+    let sick = prevalence * likelihood(sensitivity);
+    let healthy = (1. - prevalence) * likelihood(1. - specificity);
+    sick / (sick + healthy)
+}
@@ -0,0 +1,305 @@
+//! Dice and games played with them.
+
+use crate::pmf::Pmf;
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+/// Face shown by a die, counted from one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DieEvent(pub usize);
+
+impl Event for DieEvent {}
+
+/// Die with faces $1, 2, ..., \text{sides}$.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Die {
+    /// Probability of each face, starting with face one.
+    probs: Vec<f64>,
+}
+
+impl Die {
+    pub fn fair(sides: usize) -> Self {
+        assert!(sides > 0);
+        Self {
+            probs: vec![1. / sides as f64; sides],
+        }
+    }
+
+    /// Die that shows each face with probability proportional to its weight.
+    pub fn loaded(weights: &[f64]) -> Self {
+        let total: f64 = weights.iter().sum();
+        assert!(total > 0. && weights.iter().all(|w| *w >= 0.));
+        Self {
+            probs: weights.iter().map(|w| w / total).collect(),
+        }
+    }
+
+    pub fn sides(&self) -> usize {
+        self.probs.len()
+    }
+
+    pub fn event_space(&self) -> Vec<(f64, DieEvent)> {
+        self.probs
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| (p, DieEvent(i + 1)))
+            .collect()
+    }
+
+    pub fn roll(&self) -> DieEvent {
+        *select(&self.event_space())
+    }
+
+    /// Distribution of the face.
+    pub fn pmf(&self) -> Pmf {
+        Pmf::new(1, self.probs.clone())
+    }
+
+    /// Distribution of the sum of `n` rolls.
+    pub fn sum_pmf(&self, n: usize) -> Pmf {
+        (0..n).fold(Pmf::point(0), |sum, _| sum.convolve(&self.pmf()))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DiceOutcome {
+    /// Faces in the order the dice were rolled.
+    pub faces: Vec<usize>,
+}
+
+impl Outcome for DiceOutcome {}
+
+/// Roll each die once.
+#[derive(Debug, Clone)]
+pub struct DiceCondition {
+    dice: Vec<Die>,
+    faces: Vec<usize>,
+}
+
+impl DiceCondition {
+    pub fn new(dice: Vec<Die>) -> Self {
+        Self {
+            faces: Vec::with_capacity(dice.len()),
+            dice,
+        }
+    }
+}
+
+impl Condition for DiceCondition {
+    type Event = DieEvent;
+    type Outcome = DiceOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        assert!(self.faces.len() < self.dice.len());
+        self.faces.push(event.0);
+    }
+
+    fn select_event(&self) -> Self::Event {
+        self.dice[self.faces.len()].roll()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        if self.faces.len() == self.dice.len() {
+            Some(DiceOutcome {
+                faces: self.faces.clone(),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl EventSpace for DiceCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        self.dice[self.faces.len()].event_space()
+    }
+}
+
+pub struct DiceStartCondition {
+    pub dice: Vec<Die>,
+}
+
+impl StartCondition for DiceStartCondition {
+    type Event = DieEvent;
+    type Outcome = DiceOutcome;
+    type Condition = DiceCondition;
+
+    fn build(&self) -> Self::Condition {
+        DiceCondition::new(self.dice.clone())
+    }
+}
+
+/// Sum of the faces.
+pub struct DiceSumRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for DiceSumRandomVariable {
+    type Outcome = DiceOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.faces.iter().sum()
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// Highest face.
+pub struct DiceMaxRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for DiceMaxRandomVariable {
+    type Outcome = DiceOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.faces.into_iter().max().unwrap_or(0)
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// Total of a roll of two dice.
+#[derive(Debug, Clone, Copy)]
+pub struct CrapsEvent(pub usize);
+
+impl Event for CrapsEvent {}
+
+pub struct CrapsOutcome {
+    pub win: bool,
+    /// Number of rolls until the game was decided.
+    pub rolls: usize,
+}
+
+impl Outcome for CrapsOutcome {}
+
+/// Craps: the come-out roll wins on 7 or 11 and loses on 2, 3 or 12. Any other total becomes the
+/// point, which has to be rolled again before a 7.
+#[derive(Debug, Clone)]
+pub struct CrapsCondition {
+    space: Vec<(f64, CrapsEvent)>,
+    point: Option<usize>,
+    rolls: usize,
+    win: Option<bool>,
+}
+
+impl CrapsCondition {
+    pub fn new(die: &Die) -> Self {
+        Self {
+            space: die
+                .sum_pmf(2)
+                .values()
+                .map(|(total, p)| (p, CrapsEvent(total)))
+                .collect(),
+            point: None,
+            rolls: 0,
+            win: None,
+        }
+    }
+}
+
+impl Condition for CrapsCondition {
+    type Event = CrapsEvent;
+    type Outcome = CrapsOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        assert!(self.win.is_none());
+        self.rolls += 1;
+        let total = event.0;
+        match self.point {
+            None => match total {
+                7 | 11 => self.win = Some(true),
+                2 | 3 | 12 => self.win = Some(false),
+                _ => self.point = Some(total),
+            },
+            Some(point) if total == point => self.win = Some(true),
+            Some(_) if total == 7 => self.win = Some(false),
+            Some(_) => (),
+        }
+    }
+
+    fn select_event(&self) -> Self::Event {
+        *select(&self.space)
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        self.win.map(|win| CrapsOutcome {
+            win,
+            rolls: self.rolls,
+        })
+    }
+}
+
+impl EventSpace for CrapsCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        self.space.clone()
+    }
+}
+
+pub struct CrapsStartCondition {
+    /// Die both dice are copies of.
+    pub die: Die,
+}
+
+impl StartCondition for CrapsStartCondition {
+    type Event = CrapsEvent;
+    type Outcome = CrapsOutcome;
+    type Condition = CrapsCondition;
+
+    fn build(&self) -> Self::Condition {
+        CrapsCondition::new(&self.die)
+    }
+}
+
+/// Indicator of a win.
+pub struct CrapsWinRandomVariable;
+
+impl NonnegativeRandomVariable for CrapsWinRandomVariable {
+    type Outcome = CrapsOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.win as usize
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+pub struct CrapsRollsRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for CrapsRollsRandomVariable {
+    type Outcome = CrapsOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.rolls
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// Probability of winning at craps, $244 / 495$ with fair dice.
+///
+/// $P(7) + P(11) + \sum_{\text{point}} \frac{P(\text{point})^2}{P(\text{point}) + P(7)}$, where
+/// the points are all totals other than 2, 3, 7, 11 and 12, as in [`CrapsCondition`].
+pub fn craps_win_probability(die: &Die) -> f64 {
+    let totals = die.sum_pmf(2);
+    let seven = totals.prob(7);
+    let points: f64 = totals
+        .values()
+        .filter(|(total, _)| ![2, 3, 7, 11, 12].contains(total))
+        .map(|(_, p)| p)
+        .filter(|&p| p > 0.)
+        .map(|p| p * p / (p + seven))
+        .sum();
+    seven + totals.prob(11) + points
+}
@@ -0,0 +1,342 @@
+//! Stochastic SIR and SIS epidemics in a well-mixed population of `population` individuals.
+//!
+//! Every infected individual infects each susceptible one at rate `beta / population` and
+//! recovers at rate `gamma`, so that the basic reproduction number is $R_0 = \beta / \gamma$.
+//! The models follow the jump chain of the epidemic: each event is the next infection or
+//! recovery, which is all that matters for final sizes and extinction.
+//!
+//! Recovered individuals are immune in SIR and susceptible again in SIS.
+
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EpidemicEvent {
+    Infection,
+    Recovery,
+}
+
+impl Event for EpidemicEvent {}
+
+/// Probabilities of the next event being an infection or a recovery.
+fn epidemic_event_space(
+    susceptible: usize,
+    infected: usize,
+    population: usize,
+    beta: f64,
+    gamma: f64,
+) -> Vec<(f64, EpidemicEvent)> {
+    let infection = beta * susceptible as f64 * infected as f64 / population as f64;
+    let recovery = gamma * infected as f64;
+    let total = infection + recovery;
+    vec![
+        (infection / total, EpidemicEvent::Infection),
+        (recovery / total, EpidemicEvent::Recovery),
+    ]
+}
+
+#[derive(Debug, Clone)]
+pub struct SIROutcome {
+    /// Number of individuals ever infected, including the initially infected ones.
+    pub final_size: usize,
+}
+
+impl Outcome for SIROutcome {}
+
+#[derive(Debug, Clone)]
+pub struct SIRCondition {
+    population: usize,
+    beta: f64,
+    gamma: f64,
+    susceptible: usize,
+    infected: usize,
+}
+
+impl SIRCondition {
+    pub fn new(population: usize, infected: usize, beta: f64, gamma: f64) -> Self {
+        assert!(infected <= population);
+        Self {
+            population,
+            beta,
+            gamma,
+            susceptible: population - infected,
+            infected,
+        }
+    }
+}
+
+state_eq!(SIRCondition, susceptible, infected);
+
+impl Condition for SIRCondition {
+    type Event = EpidemicEvent;
+    type Outcome = SIROutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        match event {
+            EpidemicEvent::Infection => {
+                self.susceptible -= 1;
+                self.infected += 1;
+            }
+            EpidemicEvent::Recovery => self.infected -= 1,
+        }
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        if self.infected > 0 {
+            return None;
+        }
+        Some(SIROutcome {
+            final_size: self.population - self.susceptible,
+        })
+    }
+}
+
+impl EventSpace for SIRCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        epidemic_event_space(
+            self.susceptible,
+            self.infected,
+            self.population,
+            self.beta,
+            self.gamma,
+        )
+    }
+}
+
+pub struct SIRStartCondition {
+    pub population: usize,
+    /// Number of initially infected individuals.
+    pub infected: usize,
+    /// Infection rate.
+    pub beta: f64,
+    /// Recovery rate.
+    pub gamma: f64,
+}
+
+impl StartCondition for SIRStartCondition {
+    type Event = EpidemicEvent;
+    type Outcome = SIROutcome;
+    type Condition = SIRCondition;
+
+    fn build(&self) -> Self::Condition {
+        SIRCondition::new(self.population, self.infected, self.beta, self.gamma)
+    }
+}
+
+/// Number of individuals ever infected.
+pub struct SIRFinalSizeRandomVariable {
+    pub population: usize,
+}
+
+impl NonnegativeRandomVariable for SIRFinalSizeRandomVariable {
+    type Outcome = SIROutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.final_size
+    }
+
+    fn space_len(&self) -> usize {
+        self.population + 1
+    }
+}
+
+/// Indicator of a major outbreak, that is, of more than `threshold` individuals ever infected.
+pub struct SIRMajorOutbreakRandomVariable {
+    pub threshold: usize,
+}
+
+impl NonnegativeRandomVariable for SIRMajorOutbreakRandomVariable {
+    type Outcome = SIROutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        (outcome.final_size > self.threshold) as usize
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SISOutcome {
+    /// Number of infected individuals when the run stopped.
+    pub infected: usize,
+    /// Number of events until the infection died out or the run was stopped.
+    pub events: usize,
+}
+
+impl Outcome for SISOutcome {}
+
+impl SISOutcome {
+    pub fn is_extinct(&self) -> bool {
+        self.infected == 0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SISCondition {
+    population: usize,
+    beta: f64,
+    gamma: f64,
+    horizon: usize,
+    infected: usize,
+    events: usize,
+}
+
+impl SISCondition {
+    pub fn new(population: usize, infected: usize, beta: f64, gamma: f64, horizon: usize) -> Self {
+        assert!(infected <= population);
+        Self {
+            population,
+            beta,
+            gamma,
+            horizon,
+            infected,
+            events: 0,
+        }
+    }
+}
+
+state_eq!(SISCondition, infected, events);
+
+impl Condition for SISCondition {
+    type Event = EpidemicEvent;
+    type Outcome = SISOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        match event {
+            EpidemicEvent::Infection => self.infected += 1,
+            EpidemicEvent::Recovery => self.infected -= 1,
+        }
+        self.events += 1;
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        if self.infected > 0 && self.events < self.horizon {
+            return None;
+        }
+        Some(SISOutcome {
+            infected: self.infected,
+            events: self.events,
+        })
+    }
+}
+
+impl EventSpace for SISCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        epidemic_event_space(
+            self.population - self.infected,
+            self.infected,
+            self.population,
+            self.beta,
+            self.gamma,
+        )
+    }
+}
+
+pub struct SISStartCondition {
+    pub population: usize,
+    /// Number of initially infected individuals.
+    pub infected: usize,
+    /// Infection rate.
+    pub beta: f64,
+    /// Recovery rate.
+    pub gamma: f64,
+    /// Number of events after which a surviving infection counts as endemic.
+    pub horizon: usize,
+}
+
+impl StartCondition for SISStartCondition {
+    type Event = EpidemicEvent;
+    type Outcome = SISOutcome;
+    type Condition = SISCondition;
+
+    fn build(&self) -> Self::Condition {
+        SISCondition::new(
+            self.population,
+            self.infected,
+            self.beta,
+            self.gamma,
+            self.horizon,
+        )
+    }
+}
+
+/// Indicator of the infection dying out.
+pub struct SISExtinctionRandomVariable;
+
+impl NonnegativeRandomVariable for SISExtinctionRandomVariable {
+    type Outcome = SISOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.is_extinct() as usize
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+/// Number of infected individuals at the horizon.
+///
+/// Runs in which the infection died out are not tabulated.
+pub struct SISEndemicRandomVariable {
+    pub population: usize,
+}
+
+impl NonnegativeRandomVariable for SISEndemicRandomVariable {
+    type Outcome = SISOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        if outcome.is_extinct() {
+            usize::MAX
+        } else {
+            outcome.infected
+        }
+    }
+
+    fn space_len(&self) -> usize {
+        self.population + 1
+    }
+}
+
+/// Fraction $z$ of a large population infected in a major SIR outbreak.
+///
+/// The positive root of the final-size equation $z = 1 - e^{-R_0 z}$; zero if $R_0 \le 1$.
+pub fn final_size_fraction(r0: f64) -> f64 {
+    if r0 <= 1.0 {
+        return 0.0;
+    }
+    // The iteration converges to the positive root from one.
+    let mut z = 1.0;
+    for _ in 0..10_000 {
+        let next = 1.0 - (-r0 * z).exp();
+        if (next - z).abs() < 1e-15 {
+            return next;
+        }
+        z = next;
+    }
+    z
+}
+
+/// Probability that an epidemic started by `infected` individuals dies out before becoming
+/// major, $\min(1, 1 / R_0)^{\text{infected}}$.
+///
+/// The branching-process approximation of the early phase of both SIR and SIS.
+pub fn minor_outbreak_probability(r0: f64, infected: usize) -> f64 {
+    (1.0 / r0).min(1.0).powi(infected as i32)
+}
+
+/// Mean number of infected individuals in the endemic state of SIS, $N (1 - 1 / R_0)$.
+pub fn endemic_level(population: usize, r0: f64) -> f64 {
+    (population as f64 * (1.0 - 1.0 / r0)).max(0.0)
+}
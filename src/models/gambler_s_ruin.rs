@@ -0,0 +1,529 @@
+use crate::analytic::gambler_s_ruin_win;
+use crate::pmf::Pmf;
+use crate::policy::{deterministic, DecisionCondition, Policy};
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GREvent(usize);
+
+impl Event for GREvent {}
+
+#[derive(Debug)]
+pub enum GROutcome {
+    AWin,
+    BWin,
+}
+
+impl Outcome for GROutcome {}
+
+#[derive(Debug, Clone)]
+pub struct GRCondition {
+    /// Amount of money A has.
+    event: GREvent,
+    /// Criteria for A winning.
+    n: usize,
+    /// Probability of A winning.
+    p: f64,
+}
+
+impl GRCondition {
+    pub fn new(i: usize, n: usize, p: f64) -> Self {
+        Self {
+            event: GREvent(i),
+            n,
+            p,
+        }
+    }
+}
+
+impl Condition for GRCondition {
+    type Event = GREvent;
+    type Outcome = GROutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        self.event = event;
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        assert!(self.event.0 <= self.n);
+        match self.event.0 {
+            0 => Some(GROutcome::BWin),
+            x if x == self.n => Some(GROutcome::AWin),
+            _ => None,
+        }
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+}
+
+impl EventSpace for GRCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        assert!(self.event.0 > 0);
+        assert!(self.event.0 < self.n);
+        let q = 1. - self.p;
+        vec![
+            (self.p, GREvent(self.event.0 + 1)),
+            (q, GREvent(self.event.0 - 1)),
+        ]
+    }
+}
+
+pub struct GRStartCondition {
+    pub i: usize,
+    pub n: usize,
+    pub p: f64,
+}
+
+impl StartCondition for GRStartCondition {
+    type Event = GREvent;
+    type Outcome = GROutcome;
+    type Condition = GRCondition;
+
+    fn build(&self) -> Self::Condition {
+        GRCondition::new(self.i, self.n, self.p)
+    }
+}
+
+/// Indicator of A winning.
+pub struct GRRandomVariable;
+
+impl NonnegativeRandomVariable for GRRandomVariable {
+    type Outcome = GROutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        match outcome {
+            GROutcome::AWin => 1,
+            GROutcome::BWin => 0,
+        }
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+/// Boundary of the bankroll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Barrier {
+    /// The game ends once the bankroll reaches or passes the level.
+    Absorbing(usize),
+    /// The bankroll is held at the level instead of passing it.
+    Reflecting(usize),
+}
+
+/// Change of the bankroll in one round.
+#[derive(Debug, Clone)]
+pub struct GRGameEvent(pub i64);
+
+impl Event for GRGameEvent {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GRGameEnd {
+    Lower,
+    Upper,
+    /// The game was stopped at the horizon.
+    Horizon,
+}
+
+#[derive(Debug, Clone)]
+pub struct GRGameOutcome {
+    pub end: GRGameEnd,
+    /// Number of rounds played, ties included.
+    pub duration: usize,
+    pub max_bankroll: usize,
+    pub bankroll: usize,
+}
+
+impl Outcome for GRGameOutcome {}
+
+/// Gambler's ruin with an arbitrary step distribution and barriers.
+#[derive(Debug, Clone)]
+pub struct GRGameCondition {
+    bankroll: i64,
+    lower: Barrier,
+    upper: Barrier,
+    steps: Vec<(f64, GRGameEvent)>,
+    horizon: usize,
+    duration: usize,
+    max_bankroll: i64,
+    end: Option<GRGameEnd>,
+}
+
+impl GRGameCondition {
+    pub fn new(
+        bankroll: usize,
+        lower: Barrier,
+        upper: Barrier,
+        steps: &[(f64, i64)],
+        horizon: usize,
+    ) -> Self {
+        let level = |barrier: Barrier| match barrier {
+            Barrier::Absorbing(x) | Barrier::Reflecting(x) => x,
+        };
+        assert!(level(lower) <= bankroll && bankroll <= level(upper));
+        assert!(
+            steps.iter().all(|&(p, _)| p >= 0.0)
+                && (steps.iter().map(|(p, _)| p).sum::<f64>() - 1.0).abs() < 1e-9,
+            "the step probabilities must be non-negative and sum to one"
+        );
+        let mut cond = Self {
+            bankroll: bankroll as i64,
+            lower,
+            upper,
+            steps: steps.iter().map(|&(p, s)| (p, GRGameEvent(s))).collect(),
+            horizon,
+            duration: 0,
+            max_bankroll: bankroll as i64,
+            end: None,
+        };
+        cond.apply_barriers();
+        cond
+    }
+
+    fn apply_barriers(&mut self) {
+        match self.lower {
+            Barrier::Absorbing(x) if self.bankroll <= x as i64 => {
+                self.bankroll = x as i64;
+                self.end = Some(GRGameEnd::Lower);
+            }
+            Barrier::Reflecting(x) => self.bankroll = self.bankroll.max(x as i64),
+            _ => (),
+        }
+        match self.upper {
+            Barrier::Absorbing(x) if self.bankroll >= x as i64 => {
+                self.bankroll = x as i64;
+                self.end = Some(GRGameEnd::Upper);
+            }
+            Barrier::Reflecting(x) => self.bankroll = self.bankroll.min(x as i64),
+            _ => (),
+        }
+        if self.end.is_none() && self.duration >= self.horizon {
+            self.end = Some(GRGameEnd::Horizon);
+        }
+    }
+}
+
+state_eq!(GRGameCondition, bankroll, duration, max_bankroll);
+
+impl Condition for GRGameCondition {
+    type Event = GRGameEvent;
+    type Outcome = GRGameOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        assert!(self.end.is_none());
+        self.bankroll += event.0;
+        self.duration += 1;
+        self.apply_barriers();
+        self.max_bankroll = self.max_bankroll.max(self.bankroll);
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.steps).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        Some(GRGameOutcome {
+            end: self.end?,
+            duration: self.duration,
+            max_bankroll: self.max_bankroll as usize,
+            bankroll: self.bankroll as usize,
+        })
+    }
+}
+
+impl EventSpace for GRGameCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        self.steps.clone()
+    }
+}
+
+pub struct GRGameStartCondition {
+    pub bankroll: usize,
+    pub lower: Barrier,
+    pub upper: Barrier,
+    /// Probability of each change of the bankroll per round.
+    pub steps: Vec<(f64, i64)>,
+    /// Number of rounds after which the game is stopped.
+    pub horizon: usize,
+}
+
+impl GRGameStartCondition {
+    /// Unit bets won with probability `p`, tied with probability `r` and lost otherwise, between
+    /// absorbing barriers at `0` and `n`.
+    ///
+    /// Building the condition panics if $p + r > 1$.
+    pub fn with_ties(i: usize, n: usize, p: f64, r: f64) -> Self {
+        Self {
+            bankroll: i,
+            lower: Barrier::Absorbing(0),
+            upper: Barrier::Absorbing(n),
+            // Rounding may leave the loss probability just below zero.
+            steps: vec![(p, 1), (r, 0), ((1.0 - p - r).max(0.0), -1)],
+            horizon: usize::MAX,
+        }
+    }
+}
+
+impl StartCondition for GRGameStartCondition {
+    type Event = GRGameEvent;
+    type Outcome = GRGameOutcome;
+    type Condition = GRGameCondition;
+
+    fn build(&self) -> Self::Condition {
+        GRGameCondition::new(
+            self.bankroll,
+            self.lower,
+            self.upper,
+            &self.steps,
+            self.horizon,
+        )
+    }
+}
+
+/// Indicator of reaching the upper barrier.
+///
+/// Games stopped at the horizon are not tabulated.
+pub struct GRGameWinRandomVariable;
+
+impl NonnegativeRandomVariable for GRGameWinRandomVariable {
+    type Outcome = GRGameOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        match outcome.end {
+            GRGameEnd::Upper => 1,
+            GRGameEnd::Lower => 0,
+            GRGameEnd::Horizon => usize::MAX,
+        }
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+/// Number of rounds played.
+pub struct GRGameDurationRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for GRGameDurationRandomVariable {
+    type Outcome = GRGameOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.duration
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// Largest bankroll reached.
+pub struct GRGameMaxBankrollRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for GRGameMaxBankrollRandomVariable {
+    type Outcome = GRGameOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.max_bankroll
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GRBetEvent {
+    /// The gambler decides how much to bet.
+    Stake(usize),
+    Win,
+    Lose,
+}
+
+impl Event for GRBetEvent {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GRBetOutcome {
+    Win,
+    Ruin,
+}
+
+impl Outcome for GRBetOutcome {}
+
+/// How much the gambler stakes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BettingPolicy {
+    /// Always bet one unit.
+    Timid,
+    /// Bet everything, or just what is needed to reach the target.
+    Bold,
+}
+
+/// Gambler's ruin where the gambler chooses the stake of every bet, which is won with
+/// probability `p` and pays even money.
+#[derive(Debug, Clone)]
+pub struct GRBetCondition {
+    bankroll: usize,
+    target: usize,
+    p: f64,
+    stake: Option<usize>,
+    policy: BettingPolicy,
+}
+
+impl GRBetCondition {
+    pub fn new(bankroll: usize, target: usize, p: f64, policy: BettingPolicy) -> Self {
+        assert!(bankroll <= target);
+        Self {
+            bankroll,
+            target,
+            p,
+            stake: None,
+            policy,
+        }
+    }
+}
+
+state_eq!(GRBetCondition, bankroll, stake);
+
+impl Condition for GRBetCondition {
+    type Event = GRBetEvent;
+    type Outcome = GRBetOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        match (event, self.stake.take()) {
+            (GRBetEvent::Stake(stake), None) => self.stake = Some(stake),
+            (GRBetEvent::Win, Some(stake)) => self.bankroll += stake,
+            (GRBetEvent::Lose, Some(stake)) => self.bankroll -= stake,
+            _ => panic!("bets and their results must alternate"),
+        }
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        if self.bankroll == 0 {
+            Some(GRBetOutcome::Ruin)
+        } else if self.bankroll == self.target {
+            Some(GRBetOutcome::Win)
+        } else {
+            None
+        }
+    }
+}
+
+impl EventSpace for GRBetCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        match self.choices() {
+            Some(choices) => {
+                let probs = self.policy.probabilities(self, &choices);
+                probs.into_iter().zip(choices).collect()
+            }
+            None => vec![(self.p, GRBetEvent::Win), (1.0 - self.p, GRBetEvent::Lose)],
+        }
+    }
+}
+
+impl DecisionCondition for GRBetCondition {
+    /// Stakes from one unit up to the bankroll, never more than needed to reach the target.
+    fn choices(&self) -> Option<Vec<Self::Event>> {
+        if self.stake.is_some() {
+            return None;
+        }
+        let max = self.bankroll.min(self.target - self.bankroll);
+        Some((1..=max).map(GRBetEvent::Stake).collect())
+    }
+}
+
+impl Policy<GRBetCondition> for BettingPolicy {
+    fn probabilities(&self, _: &GRBetCondition, choices: &[GRBetEvent]) -> Vec<f64> {
+        match self {
+            BettingPolicy::Timid => deterministic(0, choices.len()),
+            BettingPolicy::Bold => deterministic(choices.len() - 1, choices.len()),
+        }
+    }
+}
+
+pub struct GRBetStartCondition {
+    pub bankroll: usize,
+    pub target: usize,
+    pub p: f64,
+    /// Policy used when the condition is not controlled from outside.
+    pub policy: BettingPolicy,
+}
+
+impl StartCondition for GRBetStartCondition {
+    type Event = GRBetEvent;
+    type Outcome = GRBetOutcome;
+    type Condition = GRBetCondition;
+
+    fn build(&self) -> Self::Condition {
+        GRBetCondition::new(self.bankroll, self.target, self.p, self.policy)
+    }
+}
+
+/// Indicator of reaching the target.
+pub struct GRBetRandomVariable;
+
+impl NonnegativeRandomVariable for GRBetRandomVariable {
+    type Outcome = GRBetOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        match outcome {
+            GRBetOutcome::Win => 1,
+            GRBetOutcome::Ruin => 0,
+        }
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+/// Probability of reaching `n` before `0` from `i` with unit bets won with probability `p` and
+/// lost with probability `q`; ties take the rest.
+pub fn win_probability(i: usize, n: usize, p: f64, q: f64) -> f64 {
+    gambler_s_ruin_win(i, n, p / (p + q))
+}
+
+/// Expected number of rounds until reaching `0` or `n` from `i`; see [`win_probability`].
+pub fn expected_duration(i: usize, n: usize, p: f64, q: f64) -> f64 {
+    // Ties only stretch the game by a factor of $1 / (p + q)$.
+    let s = p + q;
+    let (p, q) = (p / s, q / s);
+    let without_ties = if (p - q).abs() < 1e-12 {
+        (i * (n - i)) as f64
+    } else {
+        (i as f64 - n as f64 * gambler_s_ruin_win(i, n, p)) / (q - p)
+    };
+    without_ties / s
+}
+
+/// Distribution of the largest bankroll reached before `0` or `n`; see [`win_probability`].
+///
+/// The maximum reaches `m` exactly when the gambler would win a game with target `m`.
+pub fn max_bankroll_pmf(i: usize, n: usize, p: f64, q: f64) -> Pmf {
+    if i == 0 {
+        // Broke before the first round.
+        return Pmf::new(0, vec![1.]);
+    }
+    let reach = |m: usize| win_probability(i, m, p, q);
+    let probs = (i..=n)
+        .map(|m| {
+            if m == n {
+                reach(m)
+            } else {
+                reach(m) - reach(m + 1)
+            }
+        })
+        .collect();
+    Pmf::new(i, probs)
+}
@@ -0,0 +1,268 @@
+//! Genetic drift of an allele A in a haploid population of constant size `population`.
+//!
+//! Carriers of A have relative fitness $1 + s$. In the Wright–Fisher model every generation is
+//! drawn at once, each individual independently picking a parent in proportion to fitness. In
+//! the Moran model one individual, picked in proportion to fitness, reproduces at each step and
+//! its offspring replaces a uniformly random individual.
+
+use crate::analytic::{Binomial, Distribution};
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+#[derive(Debug, Clone)]
+pub struct DriftOutcome {
+    /// Whether A took over the population rather than being lost.
+    pub fixed: bool,
+    /// Number of generations of Wright–Fisher or steps of Moran until absorption.
+    pub time: usize,
+}
+
+impl Outcome for DriftOutcome {}
+
+/// Probability that an offspring carries A when `count` of `population` individuals do.
+fn offspring_probability(count: usize, population: usize, selection: f64) -> f64 {
+    let a = count as f64 * (1.0 + selection);
+    a / (a + (population - count) as f64)
+}
+
+fn drift_outcome(count: usize, population: usize, time: usize) -> Option<DriftOutcome> {
+    if count != 0 && count != population {
+        return None;
+    }
+    Some(DriftOutcome {
+        fixed: count == population,
+        time,
+    })
+}
+
+/// Number of carriers of A in the next generation.
+#[derive(Debug, Clone)]
+pub struct WFEvent(pub usize);
+
+impl Event for WFEvent {}
+
+#[derive(Debug, Clone)]
+pub struct WFCondition {
+    population: usize,
+    selection: f64,
+    count: usize,
+    generation: usize,
+}
+
+impl WFCondition {
+    pub fn new(population: usize, count: usize, selection: f64) -> Self {
+        assert!(count <= population);
+        Self {
+            population,
+            selection,
+            count,
+            generation: 0,
+        }
+    }
+}
+
+state_eq!(WFCondition, count, generation);
+
+impl Condition for WFCondition {
+    type Event = WFEvent;
+    type Outcome = DriftOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        self.count = event.0;
+        self.generation += 1;
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        drift_outcome(self.count, self.population, self.generation)
+    }
+}
+
+impl EventSpace for WFCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        let next = Binomial {
+            n: self.population,
+            p: offspring_probability(self.count, self.population, self.selection),
+        };
+        (0..=self.population)
+            .map(|k| (next.prob(k), WFEvent(k)))
+            .collect()
+    }
+}
+
+pub struct WFStartCondition {
+    pub population: usize,
+    /// Initial number of carriers of A.
+    pub count: usize,
+    /// Selective advantage $s$ of A.
+    pub selection: f64,
+}
+
+impl StartCondition for WFStartCondition {
+    type Event = WFEvent;
+    type Outcome = DriftOutcome;
+    type Condition = WFCondition;
+
+    fn build(&self) -> Self::Condition {
+        WFCondition::new(self.population, self.count, self.selection)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoranEvent {
+    /// A carrier of A replaces a non-carrier.
+    Gain,
+    /// A non-carrier replaces a carrier of A.
+    Loss,
+    /// The offspring replaces an individual of its own type.
+    Same,
+}
+
+impl Event for MoranEvent {}
+
+#[derive(Debug, Clone)]
+pub struct MoranCondition {
+    population: usize,
+    selection: f64,
+    count: usize,
+    steps: usize,
+}
+
+impl MoranCondition {
+    pub fn new(population: usize, count: usize, selection: f64) -> Self {
+        assert!(count <= population);
+        Self {
+            population,
+            selection,
+            count,
+            steps: 0,
+        }
+    }
+}
+
+state_eq!(MoranCondition, count, steps);
+
+impl Condition for MoranCondition {
+    type Event = MoranEvent;
+    type Outcome = DriftOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        match event {
+            MoranEvent::Gain => self.count += 1,
+            MoranEvent::Loss => self.count -= 1,
+            MoranEvent::Same => (),
+        }
+        self.steps += 1;
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        drift_outcome(self.count, self.population, self.steps)
+    }
+}
+
+impl EventSpace for MoranCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        let n = self.population as f64;
+        let born = offspring_probability(self.count, self.population, self.selection);
+        let dies = self.count as f64 / n;
+        let gain = born * (1.0 - dies);
+        let loss = (1.0 - born) * dies;
+        vec![
+            (gain, MoranEvent::Gain),
+            (loss, MoranEvent::Loss),
+            (1.0 - gain - loss, MoranEvent::Same),
+        ]
+    }
+}
+
+pub struct MoranStartCondition {
+    pub population: usize,
+    /// Initial number of carriers of A.
+    pub count: usize,
+    /// Selective advantage $s$ of A.
+    pub selection: f64,
+}
+
+impl StartCondition for MoranStartCondition {
+    type Event = MoranEvent;
+    type Outcome = DriftOutcome;
+    type Condition = MoranCondition;
+
+    fn build(&self) -> Self::Condition {
+        MoranCondition::new(self.population, self.count, self.selection)
+    }
+}
+
+/// Indicator of the fixation of A.
+pub struct FixationRandomVariable;
+
+impl NonnegativeRandomVariable for FixationRandomVariable {
+    type Outcome = DriftOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.fixed as usize
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+/// Number of generations or steps until A is fixed or lost.
+pub struct AbsorptionTimeRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for AbsorptionTimeRandomVariable {
+    type Outcome = DriftOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.time
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// Probability that A is fixed in the Moran model, starting from `count` carriers.
+///
+/// $\frac{1 - r^{-i}}{1 - r^{-N}}$ with $r = 1 + s$, and $i / N$ without selection.
+pub fn moran_fixation_probability(population: usize, count: usize, selection: f64) -> f64 {
+    if selection.abs() < 1e-12 {
+        return count as f64 / population as f64;
+    }
+    let r = 1.0 + selection;
+    (1.0 - r.powi(-(count as i32))) / (1.0 - r.powi(-(population as i32)))
+}
+
+/// Kimura's diffusion approximation of the probability that A is fixed in the Wright–Fisher
+/// model, starting from `count` carriers.
+///
+/// $\frac{1 - e^{-2 N s p}}{1 - e^{-2 N s}}$ with $p = i / N$, and $p$ without selection, where
+/// it is exact.
+pub fn kimura_fixation_probability(population: usize, count: usize, selection: f64) -> f64 {
+    let n = population as f64;
+    let p = count as f64 / n;
+    if selection.abs() < 1e-12 {
+        return p;
+    }
+    (1.0 - (-2.0 * n * selection * p).exp()) / (1.0 - (-2.0 * n * selection).exp())
+}
+
+/// Diffusion approximation of the expected number of generations until A is fixed or lost in
+/// the neutral Wright–Fisher model, $-2N (p \ln p + (1 - p) \ln (1 - p))$ with $p = i / N$.
+pub fn neutral_absorption_time(population: usize, count: usize) -> f64 {
+    let n = population as f64;
+    let p = count as f64 / n;
+    let x_ln_x = |x: f64| if x > 0.0 { x * x.ln() } else { 0.0 };
+    -2.0 * n * (x_ln_x(p) + x_ln_x(1.0 - p))
+}
@@ -0,0 +1,137 @@
+//! Matching problem: `n` letters are put into their `n` envelopes in random order.
+
+use crate::pmf::Pmf;
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+/// Envelope the next letter is put into.
+#[derive(Debug, Clone)]
+pub struct MatchingEvent(pub usize);
+
+impl Event for MatchingEvent {}
+
+pub struct MatchingOutcome {
+    /// Number of letters in their own envelope.
+    pub matches: usize,
+}
+
+impl Outcome for MatchingOutcome {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MatchingCondition {
+    /// Envelopes that are still empty, in increasing order so that equal states compare equal.
+    empty: Vec<usize>,
+    /// Letter to be put into an envelope next.
+    letter: usize,
+    matches: usize,
+}
+
+impl MatchingCondition {
+    pub fn new(n: usize) -> Self {
+        Self {
+            empty: (0..n).collect(),
+            letter: 0,
+            matches: 0,
+        }
+    }
+}
+
+impl Condition for MatchingCondition {
+    type Event = MatchingEvent;
+    type Outcome = MatchingOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        let i = self.empty.iter().position(|&e| e == event.0).unwrap();
+        self.empty.remove(i);
+        if event.0 == self.letter {
+            self.matches += 1;
+        }
+        self.letter += 1;
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        if self.empty.is_empty() {
+            Some(MatchingOutcome {
+                matches: self.matches,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl EventSpace for MatchingCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        let p = 1. / self.empty.len() as f64;
+        self.empty.iter().map(|&e| (p, MatchingEvent(e))).collect()
+    }
+}
+
+pub struct MatchingStartCondition {
+    pub n: usize,
+}
+
+impl StartCondition for MatchingStartCondition {
+    type Event = MatchingEvent;
+    type Outcome = MatchingOutcome;
+    type Condition = MatchingCondition;
+
+    fn build(&self) -> Self::Condition {
+        MatchingCondition::new(self.n)
+    }
+}
+
+pub struct MatchingRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for MatchingRandomVariable {
+    type Outcome = MatchingOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.matches
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// Probability that no letter is in its own envelope.
+///
+/// $\sum_{k=0}^n \frac{(-1)^k}{k!}$, which tends to $1 / e$.
+pub fn derangement_probability(n: usize) -> f64 {
+    alternating_exp_sum(n)
+}
+
+/// Distribution of the number of matches.
+///
+/// $P(K = k) = \frac{1}{k!} \sum_{j=0}^{n-k} \frac{(-1)^j}{j!}$
+pub fn matches_pmf(n: usize) -> Pmf {
+    let mut factorial = 1.;
+    let probs = (0..=n)
+        .map(|k| {
+            if k > 0 {
+                factorial *= k as f64;
+            }
+            alternating_exp_sum(n - k) / factorial
+        })
+        .collect();
+    Pmf::new(0, probs)
+}
+
+/// $\sum_{k=0}^n \frac{(-1)^k}{k!}$
+fn alternating_exp_sum(n: usize) -> f64 {
+    let mut term = 1.;
+    let mut sum = 1.;
+    for k in 1..=n {
+        term *= -1. / k as f64;
+        sum += term;
+    }
+    sum
+}
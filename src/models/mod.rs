@@ -0,0 +1,50 @@
+//! Ready-made models.
+
+/// Implement `PartialEq`, `Eq` and `Hash` for a condition through the listed fields, leaving out
+/// the parameters that every condition built by the same start condition shares.
+macro_rules! state_eq {
+    ($ty:ident $(<$param:ident>)?, $($field:ident),+ $(,)?) => {
+        impl$(<$param>)? PartialEq for $ty$(<$param>)? {
+            fn eq(&self, other: &Self) -> bool {
+                $(self.$field == other.$field)&&+
+            }
+        }
+
+        impl$(<$param>)? Eq for $ty$(<$param>)? {}
+
+        impl$(<$param>)? std::hash::Hash for $ty$(<$param>)? {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                $(std::hash::Hash::hash(&self.$field, state);)+
+            }
+        }
+    };
+}
+
+pub mod ballot;
+pub mod banach_matchbox;
+pub mod bernoulli;
+pub mod bertrand_box;
+pub mod binomial;
+pub mod birth_death;
+pub mod birthday;
+pub mod branching_process;
+pub mod cards;
+pub mod coupon_collector;
+pub mod diagnostic_test;
+pub mod dice;
+pub mod epidemic;
+pub mod gambler_s_ruin;
+pub mod genetic_drift;
+pub mod geometric;
+pub mod hypergeometric;
+pub mod matching;
+pub mod monty_hall;
+pub mod multinomial;
+pub mod multivariate_hypergeometric;
+pub mod negative_binomial;
+pub mod poisson;
+pub mod queue;
+pub mod random_walk;
+pub mod secretary;
+pub mod two_child;
+pub mod urn;
@@ -0,0 +1,375 @@
+use crate::policy::{DecisionCondition, Policy};
+use crate::{
+    sample_repeat, select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome,
+    StartCondition,
+};
+
+#[derive(Debug, Clone)]
+pub enum MHEvent {
+    Door1,
+    Door2,
+    Door3,
+}
+
+impl Event for MHEvent {}
+
+#[derive(Debug)]
+pub enum MHOutcome {
+    Win,
+    Lose,
+}
+
+impl Outcome for MHOutcome {}
+
+pub struct MHCondition {
+    /// Tuple format:
+    /// > (Your choice, Car's location, Host's choice, Whether you switch)
+    ///
+    /// You always switch in the end.
+    events: Vec<MHEvent>,
+}
+
+impl MHCondition {
+    pub fn new() -> Self {
+        Self {
+            events: Vec::with_capacity(3),
+        }
+    }
+}
+
+impl Default for MHCondition {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Condition for MHCondition {
+    type Event = MHEvent;
+    type Outcome = MHOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        self.events.push(event);
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        match self.events.as_slice() {
+            [MHEvent::Door1, MHEvent::Door1, MHEvent::Door2] => Some(MHOutcome::Lose),
+            [MHEvent::Door1, MHEvent::Door1, MHEvent::Door3] => Some(MHOutcome::Lose),
+            [MHEvent::Door1, MHEvent::Door2, MHEvent::Door3] => Some(MHOutcome::Win),
+            [MHEvent::Door1, MHEvent::Door3, MHEvent::Door2] => Some(MHOutcome::Win),
+            _ => None,
+        }
+    }
+
+    fn select_event(&self) -> Self::Event {
+        let space: &[(f64, Self::Event)] = match self.events.as_slice() {
+            [MHEvent::Door1] => &[
+                (1.0 / 3.0, MHEvent::Door1),
+                (1.0 / 3.0, MHEvent::Door2),
+                (1.0 / 3.0, MHEvent::Door3),
+            ],
+            [MHEvent::Door1, MHEvent::Door1] => {
+                &[(1.0 / 2.0, MHEvent::Door2), (1.0 / 2.0, MHEvent::Door3)]
+            }
+            [MHEvent::Door1, MHEvent::Door2] => &[(1.0, MHEvent::Door3)],
+            [MHEvent::Door1, MHEvent::Door3] => &[(1.0, MHEvent::Door2)],
+            _ => unreachable!(),
+        };
+        select(space).clone()
+    }
+}
+
+/// You pick door 1.
+pub struct MHStartCondition;
+
+impl StartCondition for MHStartCondition {
+    type Event = MHEvent;
+    type Outcome = MHOutcome;
+    type Condition = MHCondition;
+
+    fn build(&self) -> Self::Condition {
+        let mut cond = MHCondition::new();
+        cond.push(MHEvent::Door1);
+        cond
+    }
+}
+
+/// Indicator of winning.
+pub struct MHRandomVariable;
+
+impl NonnegativeRandomVariable for MHRandomVariable {
+    type Outcome = MHOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        match outcome {
+            MHOutcome::Win => 1,
+            MHOutcome::Lose => 0,
+        }
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+/// How the host picks the doors to open.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HostStrategy {
+    /// Opens doors hiding goats, uniformly among those the player did not pick.
+    Knows,
+    /// Opens doors the player did not pick uniformly at random, possibly revealing the car.
+    Ignorant,
+    /// Opens doors hiding goats, and whenever there is a choice, the lowest-numbered one with
+    /// probability `p` and a uniformly random other one otherwise.
+    Biased { p: f64 },
+}
+
+/// What the player does after the host opened the doors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerPolicy {
+    Stay,
+    /// Move to a uniformly random closed door other than the first pick.
+    Switch,
+    /// Pick uniformly among all closed doors, including the first pick.
+    Random,
+}
+
+impl PlayerPolicy {
+    pub const ALL: [PlayerPolicy; 3] = [
+        PlayerPolicy::Stay,
+        PlayerPolicy::Switch,
+        PlayerPolicy::Random,
+    ];
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MHGameEvent {
+    Car(usize),
+    Pick(usize),
+    Open(usize),
+    Final(usize),
+}
+
+impl Event for MHGameEvent {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MHGameOutcome {
+    Win,
+    Lose,
+    /// The ignorant host revealed the car.
+    Void,
+}
+
+impl Outcome for MHGameOutcome {}
+
+/// Monty Hall with `doors` doors of which the host opens `opened`.
+#[derive(Debug, Clone)]
+pub struct MHGameCondition {
+    doors: usize,
+    opened: usize,
+    host: HostStrategy,
+    policy: PlayerPolicy,
+    car: Option<usize>,
+    pick: Option<usize>,
+    open: Vec<usize>,
+    final_pick: Option<usize>,
+}
+
+impl MHGameCondition {
+    pub fn new(doors: usize, opened: usize, host: HostStrategy, policy: PlayerPolicy) -> Self {
+        assert!(opened + 2 <= doors);
+        Self {
+            doors,
+            opened,
+            host,
+            policy,
+            car: None,
+            pick: None,
+            open: Vec::with_capacity(opened),
+            final_pick: None,
+        }
+    }
+
+    fn is_closed(&self, door: usize) -> bool {
+        !self.open.contains(&door)
+    }
+
+    fn uniform(doors: Vec<usize>, event: fn(usize) -> MHGameEvent) -> Vec<(f64, MHGameEvent)> {
+        let p = 1.0 / doors.len() as f64;
+        doors.into_iter().map(|d| (p, event(d))).collect()
+    }
+}
+
+impl Condition for MHGameCondition {
+    type Event = MHGameEvent;
+    type Outcome = MHGameOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        match event {
+            MHGameEvent::Car(d) => self.car = Some(d),
+            MHGameEvent::Pick(d) => self.pick = Some(d),
+            MHGameEvent::Open(d) => self.open.push(d),
+            MHGameEvent::Final(d) => self.final_pick = Some(d),
+        }
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        let car = self.car?;
+        if self.open.contains(&car) {
+            return Some(MHGameOutcome::Void);
+        }
+        match self.final_pick? {
+            d if d == car => Some(MHGameOutcome::Win),
+            _ => Some(MHGameOutcome::Lose),
+        }
+    }
+}
+
+impl EventSpace for MHGameCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        let all = 0..self.doors;
+        let (car, pick) = match (self.car, self.pick) {
+            (None, _) => return Self::uniform(all.collect(), MHGameEvent::Car),
+            (Some(_), None) => return Self::uniform(all.collect(), MHGameEvent::Pick),
+            (Some(car), Some(pick)) => (car, pick),
+        };
+
+        if self.open.len() < self.opened {
+            let eligible: Vec<usize> = all
+                .filter(|&d| d != pick && self.is_closed(d))
+                .filter(|&d| self.host == HostStrategy::Ignorant || d != car)
+                .collect();
+            return match self.host {
+                HostStrategy::Biased { p } if eligible.len() > 1 => {
+                    let rest = (1.0 - p) / (eligible.len() - 1) as f64;
+                    eligible
+                        .iter()
+                        .enumerate()
+                        .map(|(i, &d)| (if i == 0 { p } else { rest }, MHGameEvent::Open(d)))
+                        .collect()
+                }
+                _ => Self::uniform(eligible, MHGameEvent::Open),
+            };
+        }
+
+        let choices = self.choices().unwrap();
+        let probs = self.policy.probabilities(self, &choices);
+        probs.into_iter().zip(choices).collect()
+    }
+}
+
+impl DecisionCondition for MHGameCondition {
+    /// The player chooses among the closed doors once the host has opened the doors.
+    fn choices(&self) -> Option<Vec<Self::Event>> {
+        if self.car.is_none() || self.pick.is_none() || self.open.len() < self.opened {
+            return None;
+        }
+        let closed = (0..self.doors).filter(|&d| self.is_closed(d));
+        Some(closed.map(MHGameEvent::Final).collect())
+    }
+}
+
+impl Policy<MHGameCondition> for PlayerPolicy {
+    fn probabilities(&self, cond: &MHGameCondition, choices: &[MHGameEvent]) -> Vec<f64> {
+        let is_pick = |e: &MHGameEvent| *e == MHGameEvent::Final(cond.pick.unwrap());
+        let weights: Vec<f64> = choices
+            .iter()
+            .map(|e| match (self, is_pick(e)) {
+                (PlayerPolicy::Stay, true) | (PlayerPolicy::Switch, false) => 1.0,
+                (PlayerPolicy::Random, _) => 1.0,
+                _ => 0.0,
+            })
+            .collect();
+        let total: f64 = weights.iter().sum();
+        weights.into_iter().map(|w| w / total).collect()
+    }
+}
+
+pub struct MHGameStartCondition {
+    pub doors: usize,
+    /// Number of doors the host opens.
+    pub opened: usize,
+    pub host: HostStrategy,
+    pub policy: PlayerPolicy,
+}
+
+impl StartCondition for MHGameStartCondition {
+    type Event = MHGameEvent;
+    type Outcome = MHGameOutcome;
+    type Condition = MHGameCondition;
+
+    fn build(&self) -> Self::Condition {
+        MHGameCondition::new(self.doors, self.opened, self.host, self.policy)
+    }
+}
+
+/// Indicator of winning.
+///
+/// Void games are not tabulated.
+pub struct MHGameRandomVariable;
+
+impl NonnegativeRandomVariable for MHGameRandomVariable {
+    type Outcome = MHGameOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        match outcome {
+            MHGameOutcome::Win => 1,
+            MHGameOutcome::Lose => 0,
+            MHGameOutcome::Void => usize::MAX,
+        }
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+/// Simulated probability of winning with each policy, given the game was not void.
+pub fn win_probabilities(
+    doors: usize,
+    opened: usize,
+    host: HostStrategy,
+    rounds: usize,
+) -> Vec<(PlayerPolicy, f64)> {
+    PlayerPolicy::ALL
+        .into_iter()
+        .map(|policy| {
+            let start = MHGameStartCondition {
+                doors,
+                opened,
+                host,
+                policy,
+            };
+            let mass = sample_repeat(start, rounds, MHGameRandomVariable);
+            (policy, mass[1] as f64 / (mass[0] + mass[1]) as f64)
+        })
+        .collect()
+}
+
+/// Probability of winning with a policy, given the game was not void.
+pub fn exact_win_probability(
+    doors: usize,
+    opened: usize,
+    host: HostStrategy,
+    policy: PlayerPolicy,
+) -> f64 {
+    let n = doors as f64;
+    let closed = (doors - opened) as f64;
+    let (stay, switch) = match host {
+        // Given the car was not revealed, it is equally likely behind every closed door.
+        HostStrategy::Ignorant => (1.0 / closed, 1.0 / closed),
+        // The bias changes which doors are opened but not how likely the first pick is right.
+        HostStrategy::Knows | HostStrategy::Biased { .. } => {
+            (1.0 / n, (n - 1.0) / n / (closed - 1.0))
+        }
+    };
+    match policy {
+        PlayerPolicy::Stay => stay,
+        PlayerPolicy::Switch => switch,
+        PlayerPolicy::Random => (stay + (closed - 1.0) * switch) / closed,
+    }
+}
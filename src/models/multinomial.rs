@@ -0,0 +1,131 @@
+//! Trials with more than two categories.
+
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+/// Category of one trial.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MultiEvent(pub usize);
+
+impl Event for MultiEvent {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiOutcome {
+    /// Number of trials in each category.
+    pub counts: Vec<usize>,
+}
+
+impl Outcome for MultiOutcome {}
+
+#[derive(Debug, Clone)]
+pub struct MultinomialCondition {
+    counts: Vec<usize>,
+    space: Vec<(f64, MultiEvent)>,
+    /// Number of trials.
+    n: usize,
+}
+
+impl MultinomialCondition {
+    pub fn new(n: usize, p: &[f64]) -> Self {
+        Self {
+            counts: vec![0; p.len()],
+            space: p
+                .iter()
+                .enumerate()
+                .map(|(i, &p)| (p, MultiEvent(i)))
+                .collect(),
+            n,
+        }
+    }
+}
+
+state_eq!(MultinomialCondition, counts);
+
+impl Condition for MultinomialCondition {
+    type Event = MultiEvent;
+    type Outcome = MultiOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        self.counts[event.0] += 1;
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.space).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        let trials: usize = self.counts.iter().sum();
+        assert!(trials <= self.n);
+        if trials == self.n {
+            Some(MultiOutcome {
+                counts: self.counts.clone(),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl EventSpace for MultinomialCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        self.space.clone()
+    }
+}
+
+pub struct MultinomialStartCondition {
+    pub n: usize,
+    /// Probability of each category.
+    pub p: Vec<f64>,
+}
+
+impl StartCondition for MultinomialStartCondition {
+    type Event = MultiEvent;
+    type Outcome = MultiOutcome;
+    type Condition = MultinomialCondition;
+
+    fn build(&self) -> Self::Condition {
+        MultinomialCondition::new(self.n, &self.p)
+    }
+}
+
+/// Count of one category.
+pub struct MultiCategoryRandomVariable {
+    pub category: usize,
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for MultiCategoryRandomVariable {
+    type Outcome = MultiOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.counts[self.category]
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// $\sum_i c_i X_i$ for the counts $X_i$ of the categories.
+pub struct MultiLinearRandomVariable {
+    pub coefficients: Vec<usize>,
+    /// Largest value tabulated.
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for MultiLinearRandomVariable {
+    type Outcome = MultiOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        self.coefficients
+            .iter()
+            .zip(&outcome.counts)
+            .map(|(c, x)| c * x)
+            .sum()
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
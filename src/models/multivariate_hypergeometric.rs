@@ -0,0 +1,77 @@
+//! Draws without replacement from a population with more than two categories.
+
+use super::multinomial::{MultiEvent, MultiOutcome};
+use crate::{select, Condition, EventSpace, StartCondition};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MultiHGeomCondition {
+    counts: Vec<usize>,
+    /// Members of each category left in the population.
+    remaining: Vec<usize>,
+    /// Number of draws.
+    n: usize,
+}
+
+impl MultiHGeomCondition {
+    pub fn new(remaining: Vec<usize>, n: usize) -> Self {
+        assert!(n <= remaining.iter().sum());
+        Self {
+            counts: vec![0; remaining.len()],
+            remaining,
+            n,
+        }
+    }
+}
+
+impl Condition for MultiHGeomCondition {
+    type Event = MultiEvent;
+    type Outcome = MultiOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        self.remaining[event.0] -= 1;
+        self.counts[event.0] += 1;
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        let draws: usize = self.counts.iter().sum();
+        assert!(draws <= self.n);
+        if draws == self.n {
+            Some(MultiOutcome {
+                counts: self.counts.clone(),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl EventSpace for MultiHGeomCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        let total: usize = self.remaining.iter().sum();
+        self.remaining
+            .iter()
+            .enumerate()
+            .map(|(i, &r)| (r as f64 / total as f64, MultiEvent(i)))
+            .collect()
+    }
+}
+
+pub struct MultiHGeomStartCondition {
+    /// Size of each category in the population.
+    pub remaining: Vec<usize>,
+    pub n: usize,
+}
+
+impl StartCondition for MultiHGeomStartCondition {
+    type Event = MultiEvent;
+    type Outcome = MultiOutcome;
+    type Condition = MultiHGeomCondition;
+
+    fn build(&self) -> Self::Condition {
+        MultiHGeomCondition::new(self.remaining.clone(), self.n)
+    }
+}
@@ -0,0 +1,166 @@
+//! Poisson process and the Poisson limit of the binomial distribution.
+
+use crate::analytic::{Binomial, Distribution, Poisson};
+use crate::continuous::{ContinuousCondition, ContinuousStartCondition};
+use crate::models::binomial::{BinRandomVariable, BinStartCondition};
+use crate::pmf::Pmf;
+use crate::{sample_repeat, Event, NonnegativeRandomVariable, Outcome};
+
+#[derive(Debug, Clone)]
+pub struct PoissonEvent;
+
+impl Event for PoissonEvent {}
+
+#[derive(Debug, Clone)]
+pub struct PoissonOutcome {
+    /// Arrival times within the horizon in increasing order.
+    pub arrivals: Vec<f64>,
+}
+
+impl Outcome for PoissonOutcome {}
+
+/// Arrivals at constant rate until the horizon.
+#[derive(Debug, Clone)]
+pub struct PoissonCondition {
+    rate: f64,
+    horizon: f64,
+    arrivals: Vec<f64>,
+}
+
+impl PoissonCondition {
+    pub fn new(rate: f64, horizon: f64) -> Self {
+        assert!(rate > 0.0 && horizon.is_finite());
+        Self {
+            rate,
+            horizon,
+            arrivals: Vec::new(),
+        }
+    }
+}
+
+impl ContinuousCondition for PoissonCondition {
+    type Event = PoissonEvent;
+    type Outcome = PoissonOutcome;
+
+    fn rates(&self) -> Vec<(f64, Self::Event)> {
+        vec![(self.rate, PoissonEvent)]
+    }
+
+    fn push(&mut self, _event: Self::Event, time: f64) {
+        self.arrivals.push(time);
+    }
+
+    fn outcome(&self, time: f64) -> Option<Self::Outcome> {
+        if time >= self.horizon {
+            Some(PoissonOutcome {
+                arrivals: self.arrivals.clone(),
+            })
+        } else {
+            None
+        }
+    }
+
+    fn horizon(&self) -> f64 {
+        self.horizon
+    }
+}
+
+pub struct PoissonStartCondition {
+    pub rate: f64,
+    pub horizon: f64,
+}
+
+impl ContinuousStartCondition for PoissonStartCondition {
+    type Event = PoissonEvent;
+    type Outcome = PoissonOutcome;
+    type Condition = PoissonCondition;
+
+    fn build(&self) -> Self::Condition {
+        PoissonCondition::new(self.rate, self.horizon)
+    }
+}
+
+/// Number of arrivals within the horizon.
+pub struct PoissonCountRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for PoissonCountRandomVariable {
+    type Outcome = PoissonOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.arrivals.len()
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// Bin of width `width` of the time between arrival `k` and the one before, where the time before
+/// arrival 0 is measured from time zero.
+///
+/// Runs with fewer than `k + 1` arrivals are not tabulated. The tabulated times are therefore
+/// conditioned on arrival `k` happening before the horizon, which truncates the exponential
+/// distribution of the gap; it is only close to exponential when the horizon is long compared to
+/// `k + 1` mean inter-arrival times.
+pub struct PoissonInterArrivalRandomVariable {
+    pub k: usize,
+    pub width: f64,
+    pub bins: usize,
+}
+
+impl NonnegativeRandomVariable for PoissonInterArrivalRandomVariable {
+    type Outcome = PoissonOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        let Some(&arrival) = outcome.arrivals.get(self.k) else {
+            return usize::MAX;
+        };
+        let previous = match self.k {
+            0 => 0.0,
+            k => outcome.arrivals[k - 1],
+        };
+        ((arrival - previous) / self.width) as usize
+    }
+
+    fn space_len(&self) -> usize {
+        self.bins
+    }
+}
+
+/// Distance of $\text{Binomial}(n, \lambda / n)$ from $\text{Poisson}(\lambda)$.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoissonLimitRow {
+    pub n: usize,
+    /// Total variation distance of the simulated binomial PMF.
+    pub simulated: f64,
+    /// Total variation distance of the exact binomial PMF.
+    pub exact: f64,
+    /// Le Cam's bound $\lambda^2 / n$ on the exact distance.
+    pub bound: f64,
+}
+
+/// Compare binomial distributions with mean `lambda` to the Poisson distribution as `n` grows.
+///
+/// The simulated distance does not drop below the sampling noise of `rounds` rounds.
+pub fn poisson_limit(lambda: f64, ns: &[usize], rounds: usize) -> Vec<PoissonLimitRow> {
+    let poisson = Poisson { lambda };
+    // Cover the Poisson tail to well below the simulated noise.
+    let tail = (lambda + 10.0 * lambda.sqrt() + 10.0).ceil() as usize;
+    ns.iter()
+        .map(|&n| {
+            let p = lambda / n as f64;
+            let reference = poisson.pmf(tail.max(n + 1));
+            let mass = sample_repeat(BinStartCondition { n, p }, rounds, BinRandomVariable { n });
+            let simulated = Pmf::from_mass(&mass, rounds);
+            let exact = Binomial { n, p }.pmf(n + 1);
+            PoissonLimitRow {
+                n,
+                simulated: simulated.total_variation(&reference),
+                exact: exact.total_variation(&reference),
+                bound: lambda * lambda / n as f64,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,298 @@
+//! Markovian queues: M/M/c with an optional limit on the number of customers in the system.
+//!
+//! Customers arrive at rate `arrival`, each of the `servers` serves at rate `service` and
+//! customers are served in order of arrival. A customer who finds the system full is lost.
+//!
+//! A run starts from an empty system and follows the customer arriving after `warmup` others,
+//! until that customer starts service or is lost. Poisson arrivals see the stationary
+//! distribution of the system, so after a long enough warm-up the outcome is a sample of the
+//! steady state.
+
+use crate::continuous::{ContinuousCondition, ContinuousStartCondition};
+use crate::pmf::Pmf;
+use crate::{Event, NonnegativeRandomVariable, Outcome};
+
+#[derive(Debug, Clone)]
+pub enum QueueEvent {
+    Arrival,
+    Departure,
+}
+
+impl Event for QueueEvent {}
+
+#[derive(Debug, Clone)]
+pub struct QueueOutcome {
+    /// Number of customers the tagged customer found in the system.
+    pub found: usize,
+    /// Time the tagged customer waited for service; `None` if lost.
+    pub wait: Option<f64>,
+}
+
+impl Outcome for QueueOutcome {}
+
+#[derive(Debug, Clone)]
+struct Tagged {
+    found: usize,
+    arrival: f64,
+    /// Departures needed before the tagged customer is served.
+    ahead: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct QueueCondition {
+    arrival: f64,
+    service: f64,
+    servers: usize,
+    capacity: Option<usize>,
+    warmup: usize,
+    arrivals: usize,
+    in_system: usize,
+    tagged: Option<Tagged>,
+    outcome: Option<QueueOutcome>,
+}
+
+impl QueueCondition {
+    pub fn new(
+        arrival: f64,
+        service: f64,
+        servers: usize,
+        capacity: Option<usize>,
+        warmup: usize,
+    ) -> Self {
+        assert!(servers > 0);
+        assert!(capacity.is_none_or(|k| k >= servers));
+        Self {
+            arrival,
+            service,
+            servers,
+            capacity,
+            warmup,
+            arrivals: 0,
+            in_system: 0,
+            tagged: None,
+            outcome: None,
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.capacity.is_some_and(|k| self.in_system >= k)
+    }
+
+    fn tag(&mut self, time: f64) {
+        let found = self.in_system;
+        if self.is_full() {
+            self.outcome = Some(QueueOutcome { found, wait: None });
+        } else if found < self.servers {
+            self.outcome = Some(QueueOutcome {
+                found,
+                wait: Some(0.0),
+            });
+        } else {
+            self.tagged = Some(Tagged {
+                found,
+                arrival: time,
+                ahead: found - self.servers + 1,
+            });
+        }
+    }
+}
+
+impl ContinuousCondition for QueueCondition {
+    type Event = QueueEvent;
+    type Outcome = QueueOutcome;
+
+    fn rates(&self) -> Vec<(f64, Self::Event)> {
+        let busy = self.in_system.min(self.servers) as f64;
+        vec![
+            (self.arrival, QueueEvent::Arrival),
+            (busy * self.service, QueueEvent::Departure),
+        ]
+    }
+
+    fn push(&mut self, event: Self::Event, time: f64) {
+        match event {
+            QueueEvent::Arrival => {
+                if self.arrivals == self.warmup {
+                    self.tag(time);
+                }
+                self.arrivals += 1;
+                if !self.is_full() {
+                    self.in_system += 1;
+                }
+            }
+            QueueEvent::Departure => {
+                self.in_system -= 1;
+                if let Some(tagged) = &mut self.tagged {
+                    tagged.ahead -= 1;
+                    if tagged.ahead == 0 {
+                        self.outcome = Some(QueueOutcome {
+                            found: tagged.found,
+                            wait: Some(time - tagged.arrival),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn outcome(&self, _time: f64) -> Option<Self::Outcome> {
+        self.outcome.clone()
+    }
+}
+
+pub struct QueueStartCondition {
+    /// Arrival rate.
+    pub arrival: f64,
+    /// Service rate of each server.
+    pub service: f64,
+    pub servers: usize,
+    /// Largest number of customers in the system, including those in service.
+    pub capacity: Option<usize>,
+    /// Number of customers arriving before the one that is followed.
+    pub warmup: usize,
+}
+
+impl ContinuousStartCondition for QueueStartCondition {
+    type Event = QueueEvent;
+    type Outcome = QueueOutcome;
+    type Condition = QueueCondition;
+
+    fn build(&self) -> Self::Condition {
+        QueueCondition::new(
+            self.arrival,
+            self.service,
+            self.servers,
+            self.capacity,
+            self.warmup,
+        )
+    }
+}
+
+/// Number of customers found in the system on arrival.
+pub struct QueueLengthRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for QueueLengthRandomVariable {
+    type Outcome = QueueOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.found
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// Bin of width `width` of the waiting time.
+///
+/// Lost customers are not tabulated.
+pub struct QueueWaitRandomVariable {
+    pub width: f64,
+    pub bins: usize,
+}
+
+impl NonnegativeRandomVariable for QueueWaitRandomVariable {
+    type Outcome = QueueOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        match outcome.wait {
+            Some(wait) => (wait / self.width) as usize,
+            None => usize::MAX,
+        }
+    }
+
+    fn space_len(&self) -> usize {
+        self.bins
+    }
+}
+
+/// Indicator of having to wait for service.
+///
+/// Lost customers are not tabulated.
+pub struct QueueDelayRandomVariable;
+
+impl NonnegativeRandomVariable for QueueDelayRandomVariable {
+    type Outcome = QueueOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        match outcome.wait {
+            Some(wait) => (wait > 0.0) as usize,
+            None => usize::MAX,
+        }
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+/// Indicator of being lost.
+pub struct QueueLossRandomVariable;
+
+impl NonnegativeRandomVariable for QueueLossRandomVariable {
+    type Outcome = QueueOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.wait.is_none() as usize
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+/// Erlang B formula: probability that a customer is lost in M/M/c/c with offered load
+/// $a = \lambda / \mu$.
+pub fn erlang_b(servers: usize, load: f64) -> f64 {
+    (1..=servers).fold(1.0, |b, k| load * b / (k as f64 + load * b))
+}
+
+/// Erlang C formula: probability that a customer has to wait in M/M/c with offered load
+/// $a = \lambda / \mu < c$.
+pub fn erlang_c(servers: usize, load: f64) -> f64 {
+    let c = servers as f64;
+    assert!(load < c);
+    let b = erlang_b(servers, load);
+    c * b / (c - load * (1.0 - b))
+}
+
+/// Expected waiting time in M/M/c, $\frac{C(c, a)}{c \mu - \lambda}$.
+pub fn mmc_mean_wait(arrival: f64, service: f64, servers: usize) -> f64 {
+    erlang_c(servers, arrival / service) / (servers as f64 * service - arrival)
+}
+
+/// $P(W > t)$ in M/M/c, $C(c, a) e^{-(c \mu - \lambda) t}$.
+pub fn mmc_wait_survival(arrival: f64, service: f64, servers: usize, t: f64) -> f64 {
+    let c = servers as f64;
+    erlang_c(servers, arrival / service) * (-(c * service - arrival) * t).exp()
+}
+
+/// Stationary distribution of the number of customers in M/M/c/K on $0, 1, ..., \text{len} - 1$;
+/// M/M/c if there is no capacity.
+pub fn stationary_pmf(
+    arrival: f64,
+    service: f64,
+    servers: usize,
+    capacity: Option<usize>,
+    len: usize,
+) -> Pmf {
+    let load = arrival / service;
+    let c = servers as f64;
+    // Unnormalized probabilities $\frac{a^n}{n!}$ up to `servers`, growing by $a / c$ beyond.
+    let weight = |n: usize| -> f64 {
+        if capacity.is_some_and(|k| n > k) {
+            return 0.0;
+        }
+        (1..=n).map(|i| load / i.min(servers) as f64).product()
+    };
+    let total: f64 = match capacity {
+        Some(k) => (0..=k).map(weight).sum(),
+        None => {
+            assert!(load < c);
+            (0..servers).map(weight).sum::<f64>() + weight(servers) / (1.0 - load / c)
+        }
+    };
+    Pmf::new(0, (0..len).map(|n| weight(n) / total).collect())
+}
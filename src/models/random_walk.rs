@@ -0,0 +1,479 @@
+//! Random walks on the lattice $\mathbb{Z}^d$ and on finite graphs.
+//!
+//! A walk stops when it enters one of its absorbing sets or after `max_steps` steps. Absorption is
+//! only checked after the first step, so that taking the starting point as an absorbing set
+//! gives the first return time.
+
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+#[derive(Debug, Clone)]
+pub struct WalkOutcome {
+    /// Index of the absorbing set the walk entered; `None` if it was stopped at `max_steps`.
+    pub absorbed: Option<usize>,
+    pub steps: usize,
+    /// Number of visits to the starting point after the start.
+    pub returns: usize,
+}
+
+impl Outcome for WalkOutcome {}
+
+/// Unit step along one axis of the lattice.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatticeEvent {
+    pub axis: usize,
+    pub forward: bool,
+}
+
+impl Event for LatticeEvent {}
+
+/// Simple random walk on $\mathbb{Z}^d$, stepping to each of the $2d$ neighbors with equal
+/// probability.
+///
+/// `absorbing` maps a point to the index of the absorbing set containing it.
+#[derive(Debug, Clone)]
+pub struct LatticeCondition<F> {
+    origin: Vec<i64>,
+    absorbing: F,
+    max_steps: usize,
+    position: Vec<i64>,
+    steps: usize,
+    returns: usize,
+}
+
+impl<F> LatticeCondition<F>
+where
+    F: Fn(&[i64]) -> Option<usize>,
+{
+    pub fn new(origin: Vec<i64>, absorbing: F, max_steps: usize) -> Self {
+        assert!(!origin.is_empty());
+        Self {
+            position: origin.clone(),
+            origin,
+            absorbing,
+            max_steps,
+            steps: 0,
+            returns: 0,
+        }
+    }
+
+    pub fn position(&self) -> &[i64] {
+        &self.position
+    }
+}
+
+state_eq!(LatticeCondition<F>, position, steps, returns);
+
+impl<F> Condition for LatticeCondition<F>
+where
+    F: Fn(&[i64]) -> Option<usize>,
+{
+    type Event = LatticeEvent;
+    type Outcome = WalkOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        self.position[event.axis] += if event.forward { 1 } else { -1 };
+        self.steps += 1;
+        if self.position == self.origin {
+            self.returns += 1;
+        }
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        let absorbed = match self.steps {
+            0 => None,
+            _ => (self.absorbing)(&self.position),
+        };
+        if absorbed.is_none() && self.steps < self.max_steps {
+            return None;
+        }
+        Some(WalkOutcome {
+            absorbed,
+            steps: self.steps,
+            returns: self.returns,
+        })
+    }
+}
+
+impl<F> EventSpace for LatticeCondition<F>
+where
+    F: Fn(&[i64]) -> Option<usize>,
+{
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        let d = self.position.len();
+        let p = 1.0 / (2 * d) as f64;
+        (0..d)
+            .flat_map(|axis| [true, false].map(|forward| (p, LatticeEvent { axis, forward })))
+            .collect()
+    }
+}
+
+pub struct LatticeStartCondition<F> {
+    pub origin: Vec<i64>,
+    /// Index of the absorbing set containing a point, if any.
+    pub absorbing: F,
+    /// Number of steps after which the walk is stopped.
+    pub max_steps: usize,
+}
+
+impl<F> StartCondition for LatticeStartCondition<F>
+where
+    F: Fn(&[i64]) -> Option<usize> + Clone,
+{
+    type Event = LatticeEvent;
+    type Outcome = WalkOutcome;
+    type Condition = LatticeCondition<F>;
+
+    fn build(&self) -> Self::Condition {
+        LatticeCondition::new(self.origin.clone(), self.absorbing.clone(), self.max_steps)
+    }
+}
+
+/// Finite graph with transition probabilities along its edges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Graph {
+    transitions: Vec<Vec<(f64, usize)>>,
+}
+
+impl Graph {
+    /// Walk along the edges out of every vertex in proportion to their weights.
+    pub fn from_weights(weights: Vec<Vec<(f64, usize)>>) -> Self {
+        let n = weights.len();
+        let transitions = weights
+            .into_iter()
+            .map(|edges| {
+                assert!(edges.iter().all(|&(w, v)| w >= 0.0 && v < n));
+                let total: f64 = edges.iter().map(|(w, _)| w).sum();
+                assert!(total > 0.0, "every vertex needs an edge out");
+                edges.into_iter().map(|(w, v)| (w / total, v)).collect()
+            })
+            .collect();
+        Self { transitions }
+    }
+
+    /// Simple random walk on an undirected graph, moving to a uniformly random neighbor.
+    pub fn from_edges(vertices: usize, edges: &[(usize, usize)]) -> Self {
+        let mut weights = vec![Vec::new(); vertices];
+        for &(u, v) in edges {
+            weights[u].push((1.0, v));
+            if u != v {
+                weights[v].push((1.0, u));
+            }
+        }
+        Self::from_weights(weights)
+    }
+
+    /// Cycle $0, 1, ..., n - 1$.
+    pub fn cycle(n: usize) -> Self {
+        let edges: Vec<_> = (0..n).map(|v| (v, (v + 1) % n)).collect();
+        Self::from_edges(n, &edges)
+    }
+
+    pub fn len(&self) -> usize {
+        self.transitions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transitions.is_empty()
+    }
+
+    /// Transition probabilities out of `vertex`.
+    pub fn transitions(&self, vertex: usize) -> &[(f64, usize)] {
+        &self.transitions[vertex]
+    }
+
+    /// Probability of entering each absorbing set first, starting from `start`.
+    ///
+    /// Solves the harmonic equations by Gauss–Seidel iteration. The probabilities do not sum to
+    /// one if the walk can avoid all sets forever.
+    pub fn hitting_probabilities(&self, absorbing: &[Vec<usize>], start: usize) -> Vec<f64> {
+        let labels = self.labels(absorbing);
+        (0..absorbing.len())
+            .map(|set| {
+                let h = self.solve(|v| labels[v].map(|s| if s == set { 1.0 } else { 0.0 }), 0.0);
+                self.step(&h, start)
+            })
+            .collect()
+    }
+
+    /// Expected number of steps until the walk enters `target`, starting from `start`.
+    ///
+    /// Starting in `target` gives the expected return time. The expectation is infinite if the
+    /// walk can reach a vertex from which the target is unreachable.
+    pub fn expected_hitting_time(&self, target: &[usize], start: usize) -> f64 {
+        let reaches = self.reaching(target);
+        let mut seen = vec![false; self.len()];
+        let mut stack = vec![start];
+        while let Some(v) = stack.pop() {
+            for &(p, u) in &self.transitions[v] {
+                if p == 0.0 || target.contains(&u) || seen[u] {
+                    continue;
+                }
+                if !reaches[u] {
+                    return f64::INFINITY;
+                }
+                seen[u] = true;
+                stack.push(u);
+            }
+        }
+        // The vertices that cannot reach the target are never visited, so their value is unused.
+        let t = self.solve(|v| (target.contains(&v) || !reaches[v]).then_some(0.0), 1.0);
+        1.0 + self.step(&t, start)
+    }
+
+    /// Whether the walk can get from each vertex into `target`.
+    fn reaching(&self, target: &[usize]) -> Vec<bool> {
+        let mut into = vec![Vec::new(); self.len()];
+        for (v, edges) in self.transitions.iter().enumerate() {
+            for &(p, u) in edges {
+                if p > 0.0 {
+                    into[u].push(v);
+                }
+            }
+        }
+        let mut reaches = vec![false; self.len()];
+        let mut stack = target.to_vec();
+        while let Some(u) = stack.pop() {
+            if !reaches[u] {
+                reaches[u] = true;
+                stack.extend(&into[u]);
+            }
+        }
+        reaches
+    }
+
+    fn labels(&self, absorbing: &[Vec<usize>]) -> Vec<Option<usize>> {
+        let mut labels = vec![None; self.len()];
+        for (i, set) in absorbing.iter().enumerate() {
+            for &v in set {
+                labels[v] = Some(i);
+            }
+        }
+        labels
+    }
+
+    /// $\sum_u p(v, u) x(u)$
+    fn step(&self, x: &[f64], v: usize) -> f64 {
+        self.transitions[v].iter().map(|&(p, u)| p * x[u]).sum()
+    }
+
+    /// Solve $x(v) = c + \sum_u p(v, u) x(u)$ off the vertices with a boundary value.
+    fn solve(&self, boundary: impl Fn(usize) -> Option<f64>, c: f64) -> Vec<f64> {
+        let mut x: Vec<f64> = (0..self.len())
+            .map(|v| boundary(v).unwrap_or(0.0))
+            .collect();
+        for _ in 0..1_000_000 {
+            let mut change: f64 = 0.0;
+            for v in 0..self.len() {
+                if boundary(v).is_some() {
+                    continue;
+                }
+                let next = c + self.step(&x, v);
+                change = change.max((next - x[v]).abs() / next.abs().max(1.0));
+                x[v] = next;
+            }
+            if change < 1e-14 {
+                break;
+            }
+        }
+        x
+    }
+}
+
+/// Next vertex of the walk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphEvent(pub usize);
+
+impl Event for GraphEvent {}
+
+#[derive(Debug, Clone)]
+pub struct GraphCondition {
+    graph: Graph,
+    /// Index of the absorbing set containing each vertex.
+    labels: Vec<Option<usize>>,
+    start: usize,
+    max_steps: usize,
+    vertex: usize,
+    steps: usize,
+    returns: usize,
+}
+
+impl GraphCondition {
+    pub fn new(graph: Graph, absorbing: &[Vec<usize>], start: usize, max_steps: usize) -> Self {
+        assert!(start < graph.len());
+        Self {
+            labels: graph.labels(absorbing),
+            graph,
+            start,
+            max_steps,
+            vertex: start,
+            steps: 0,
+            returns: 0,
+        }
+    }
+}
+
+state_eq!(GraphCondition, vertex, steps, returns);
+
+impl Condition for GraphCondition {
+    type Event = GraphEvent;
+    type Outcome = WalkOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        self.vertex = event.0;
+        self.steps += 1;
+        if self.vertex == self.start {
+            self.returns += 1;
+        }
+    }
+
+    fn select_event(&self) -> Self::Event {
+        GraphEvent(*select(self.graph.transitions(self.vertex)))
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        let absorbed = match self.steps {
+            0 => None,
+            _ => self.labels[self.vertex],
+        };
+        if absorbed.is_none() && self.steps < self.max_steps {
+            return None;
+        }
+        Some(WalkOutcome {
+            absorbed,
+            steps: self.steps,
+            returns: self.returns,
+        })
+    }
+}
+
+impl EventSpace for GraphCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        self.graph
+            .transitions(self.vertex)
+            .iter()
+            .map(|&(p, v)| (p, GraphEvent(v)))
+            .collect()
+    }
+}
+
+pub struct GraphStartCondition {
+    pub graph: Graph,
+    /// Sets of vertices that stop the walk.
+    pub absorbing: Vec<Vec<usize>>,
+    pub start: usize,
+    /// Number of steps after which the walk is stopped.
+    pub max_steps: usize,
+}
+
+impl StartCondition for GraphStartCondition {
+    type Event = GraphEvent;
+    type Outcome = WalkOutcome;
+    type Condition = GraphCondition;
+
+    fn build(&self) -> Self::Condition {
+        GraphCondition::new(
+            self.graph.clone(),
+            &self.absorbing,
+            self.start,
+            self.max_steps,
+        )
+    }
+}
+
+/// Index of the absorbing set the walk entered; `sets` if it entered none.
+pub struct HittingRandomVariable {
+    pub sets: usize,
+}
+
+impl NonnegativeRandomVariable for HittingRandomVariable {
+    type Outcome = WalkOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.absorbed.unwrap_or(self.sets)
+    }
+
+    fn space_len(&self) -> usize {
+        self.sets + 1
+    }
+}
+
+/// Number of steps until the walk entered an absorbing set.
+///
+/// Walks stopped at `max_steps` are not tabulated.
+pub struct FirstPassageRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for FirstPassageRandomVariable {
+    type Outcome = WalkOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        match outcome.absorbed {
+            Some(_) => outcome.steps,
+            None => usize::MAX,
+        }
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// Number of returns to the starting point.
+pub struct ReturnsRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for ReturnsRandomVariable {
+    type Outcome = WalkOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.returns
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// $P(S_{2k} = 0) = \binom{2k}{k} 4^{-k}$ for $k = 0, 1, ..., \lfloor \text{steps} / 2 \rfloor$ for
+/// the simple random walk on $\mathbb{Z}$.
+fn simple_walk_at_origin(steps: usize) -> impl Iterator<Item = f64> {
+    (0..=steps / 2).scan(1.0, |u, k| {
+        if k > 0 {
+            *u *= (2 * k - 1) as f64 / (2 * k) as f64;
+        }
+        Some(*u)
+    })
+}
+
+/// Probability that the simple random walk on $\mathbb{Z}$ returns to the origin within `steps`
+/// steps, $1 - \binom{2k}{k} 4^{-k}$ with $k = \lfloor \text{steps} / 2 \rfloor$.
+pub fn simple_walk_return_probability_by(steps: usize) -> f64 {
+    1.0 - simple_walk_at_origin(steps).last().unwrap()
+}
+
+/// Expected number of returns of the simple random walk on $\mathbb{Z}$ to the origin within
+/// `steps` steps.
+pub fn simple_walk_expected_returns(steps: usize) -> f64 {
+    simple_walk_at_origin(steps).skip(1).sum()
+}
+
+/// Probability that the simple random walk on $\mathbb{Z}^d$ ever returns to the origin.
+///
+/// One in one and two dimensions by Pólya's theorem; $1 - 1 / u_3$ with Watson's integral
+/// $u_3 \approx 1.516386$ in three. There is no closed form in more dimensions, which gives
+/// `None`.
+pub fn polya_return_probability(d: usize) -> Option<f64> {
+    match d {
+        1 | 2 => Some(1.0),
+        3 => Some(1.0 - 1.0 / 1.516_386_059_151_978),
+        _ => None,
+    }
+}
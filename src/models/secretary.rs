@@ -0,0 +1,189 @@
+//! Secretary problem: `n` candidates arrive in random order and each has to be accepted or
+//! rejected on the spot, knowing only whether they are the best seen so far.
+
+use crate::policy::{deterministic, DecisionCondition, Policy};
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretaryEvent {
+    /// The next candidate arrives, who may be the best so far.
+    Arrive {
+        best_so_far: bool,
+    },
+    Accept,
+    Reject,
+}
+
+impl Event for SecretaryEvent {}
+
+pub struct SecretaryOutcome {
+    /// Position of the accepted candidate, counted from one.
+    pub accepted: usize,
+    /// Whether the accepted candidate is the best of all.
+    pub best: bool,
+}
+
+impl Outcome for SecretaryOutcome {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SecretaryCondition {
+    n: usize,
+    /// Number of candidates that have arrived.
+    arrived: usize,
+    /// Whether the last candidate to arrive is the best so far, while waiting for a decision.
+    pending: Option<bool>,
+    /// Position of the accepted candidate and whether they were the best so far.
+    accepted: Option<(usize, bool)>,
+    /// Whether a better candidate arrived after the accepted one.
+    beaten: bool,
+    policy: Cutoff,
+}
+
+impl SecretaryCondition {
+    pub fn new(n: usize, policy: Cutoff) -> Self {
+        assert!(n > 0);
+        Self {
+            n,
+            arrived: 0,
+            pending: None,
+            accepted: None,
+            beaten: false,
+            policy,
+        }
+    }
+}
+
+impl Condition for SecretaryCondition {
+    type Event = SecretaryEvent;
+    type Outcome = SecretaryOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        match event {
+            SecretaryEvent::Arrive { best_so_far } => {
+                assert!(self.pending.is_none() && self.arrived < self.n);
+                self.arrived += 1;
+                if self.accepted.is_some() {
+                    self.beaten |= best_so_far;
+                } else {
+                    self.pending = Some(best_so_far);
+                }
+            }
+            SecretaryEvent::Accept => {
+                let best_so_far = self.pending.take().unwrap();
+                self.accepted = Some((self.arrived, best_so_far));
+            }
+            SecretaryEvent::Reject => {
+                self.pending.take().unwrap();
+            }
+        }
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        let (accepted, best_so_far) = self.accepted?;
+        if self.arrived < self.n {
+            return None;
+        }
+        Some(SecretaryOutcome {
+            accepted,
+            best: best_so_far && !self.beaten,
+        })
+    }
+}
+
+impl EventSpace for SecretaryCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        if let Some(choices) = self.choices() {
+            let probs = self.policy.probabilities(self, &choices);
+            return probs.into_iter().zip(choices).collect();
+        }
+        // The `k`-th candidate is the best of the first `k` with probability `1 / k`.
+        let p = 1.0 / (self.arrived + 1) as f64;
+        vec![
+            (p, SecretaryEvent::Arrive { best_so_far: true }),
+            (1.0 - p, SecretaryEvent::Arrive { best_so_far: false }),
+        ]
+    }
+}
+
+impl DecisionCondition for SecretaryCondition {
+    /// The last candidate has to be accepted if all others were rejected.
+    fn choices(&self) -> Option<Vec<Self::Event>> {
+        self.pending?;
+        if self.arrived == self.n {
+            Some(vec![SecretaryEvent::Accept])
+        } else {
+            Some(vec![SecretaryEvent::Accept, SecretaryEvent::Reject])
+        }
+    }
+}
+
+/// Reject the first `r - 1` candidates, then accept the first one who is the best so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cutoff {
+    pub r: usize,
+}
+
+impl Policy<SecretaryCondition> for Cutoff {
+    fn probabilities(&self, cond: &SecretaryCondition, choices: &[SecretaryEvent]) -> Vec<f64> {
+        let accept = cond.arrived >= self.r && cond.pending == Some(true);
+        let index = if accept || choices.len() == 1 { 0 } else { 1 };
+        deterministic(index, choices.len())
+    }
+}
+
+pub struct SecretaryStartCondition {
+    pub n: usize,
+    /// Policy used when the condition is not controlled from outside.
+    pub policy: Cutoff,
+}
+
+impl StartCondition for SecretaryStartCondition {
+    type Event = SecretaryEvent;
+    type Outcome = SecretaryOutcome;
+    type Condition = SecretaryCondition;
+
+    fn build(&self) -> Self::Condition {
+        SecretaryCondition::new(self.n, self.policy)
+    }
+}
+
+/// Indicator of accepting the best candidate.
+pub struct SecretaryRandomVariable;
+
+impl NonnegativeRandomVariable for SecretaryRandomVariable {
+    type Outcome = SecretaryOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.best as usize
+    }
+
+    fn space_len(&self) -> usize {
+        2
+    }
+}
+
+/// Probability that the cutoff rule accepts the best of `n` candidates.
+///
+/// $\frac{r - 1}{n} \sum_{i=r}^n \frac{1}{i - 1}$, or $1 / n$ for $r = 1$.
+pub fn cutoff_success_probability(n: usize, r: usize) -> f64 {
+    if r <= 1 {
+        return 1.0 / n as f64;
+    }
+    (r - 1) as f64 / n as f64 * (r..=n).map(|i| 1.0 / (i - 1) as f64).sum::<f64>()
+}
+
+/// Cutoff with the highest success probability, close to $n / e$.
+pub fn optimal_cutoff(n: usize) -> Cutoff {
+    let r = (1..=n)
+        .max_by(|&a, &b| {
+            cutoff_success_probability(n, a).total_cmp(&cutoff_success_probability(n, b))
+        })
+        .unwrap();
+    Cutoff { r }
+}
@@ -0,0 +1,179 @@
+//! Two-child problem: a family has two children, each a boy or a girl born on a uniformly random
+//! weekday. What is learned about the children decides the probability of two boys.
+
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sex {
+    Boy,
+    Girl,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Child {
+    pub sex: Sex,
+    /// Weekday of birth, with Monday as zero.
+    pub weekday: usize,
+}
+
+#[derive(Debug, Clone)]
+pub enum TwoChildEvent {
+    /// Next child of the family, the older one first.
+    Born(Child),
+    /// Index of the child that is met at random.
+    Met(usize),
+}
+
+impl Event for TwoChildEvent {}
+
+#[derive(Debug, Clone)]
+pub struct TwoChildOutcome {
+    /// The older child first.
+    pub children: [Child; 2],
+    /// Index of the child that was met.
+    pub met: usize,
+}
+
+impl Outcome for TwoChildOutcome {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TwoChildCondition {
+    children: Vec<Child>,
+    met: Option<usize>,
+}
+
+impl TwoChildCondition {
+    pub fn new() -> Self {
+        Self {
+            children: Vec::with_capacity(2),
+            met: None,
+        }
+    }
+}
+
+impl Default for TwoChildCondition {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Condition for TwoChildCondition {
+    type Event = TwoChildEvent;
+    type Outcome = TwoChildOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        match event {
+            TwoChildEvent::Born(child) => {
+                assert!(self.children.len() < 2);
+                self.children.push(child);
+            }
+            TwoChildEvent::Met(i) => {
+                assert!(self.children.len() == 2 && self.met.is_none());
+                self.met = Some(i);
+            }
+        }
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        self.met.map(|met| TwoChildOutcome {
+            children: [self.children[0], self.children[1]],
+            met,
+        })
+    }
+}
+
+impl EventSpace for TwoChildCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        if self.children.len() == 2 {
+            return vec![(0.5, TwoChildEvent::Met(0)), (0.5, TwoChildEvent::Met(1))];
+        }
+        [Sex::Boy, Sex::Girl]
+            .iter()
+            .flat_map(|&sex| {
+                (0..7).map(move |weekday| (1. / 14., TwoChildEvent::Born(Child { sex, weekday })))
+            })
+            .collect()
+    }
+}
+
+pub struct TwoChildStartCondition;
+
+impl StartCondition for TwoChildStartCondition {
+    type Event = TwoChildEvent;
+    type Outcome = TwoChildOutcome;
+    type Condition = TwoChildCondition;
+
+    fn build(&self) -> Self::Condition {
+        TwoChildCondition::new()
+    }
+}
+
+/// Number of boys.
+pub struct TwoChildBoysRandomVariable;
+
+impl NonnegativeRandomVariable for TwoChildBoysRandomVariable {
+    type Outcome = TwoChildOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome
+            .children
+            .iter()
+            .filter(|child| child.sex == Sex::Boy)
+            .count()
+    }
+
+    fn space_len(&self) -> usize {
+        3
+    }
+}
+
+/// What is known about the family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TwoChildEvidence {
+    /// At least one of the children is a boy.
+    AtLeastOneBoy,
+    /// The older child is a boy.
+    OlderIsBoy,
+    /// At least one of the children is a boy born on a Tuesday.
+    BoyBornOnTuesday,
+    /// The child met at random is a boy.
+    MetBoy,
+}
+
+impl TwoChildEvidence {
+    pub const ALL: [TwoChildEvidence; 4] = [
+        TwoChildEvidence::AtLeastOneBoy,
+        TwoChildEvidence::OlderIsBoy,
+        TwoChildEvidence::BoyBornOnTuesday,
+        TwoChildEvidence::MetBoy,
+    ];
+
+    /// Whether the outcome is consistent with the evidence.
+    pub fn holds(&self, outcome: &TwoChildOutcome) -> bool {
+        let [older, _] = outcome.children;
+        match self {
+            TwoChildEvidence::AtLeastOneBoy => outcome.children.iter().any(|c| c.sex == Sex::Boy),
+            TwoChildEvidence::OlderIsBoy => older.sex == Sex::Boy,
+            TwoChildEvidence::BoyBornOnTuesday => outcome
+                .children
+                .iter()
+                .any(|c| c.sex == Sex::Boy && c.weekday == 1),
+            TwoChildEvidence::MetBoy => outcome.children[outcome.met].sex == Sex::Boy,
+        }
+    }
+
+    /// Probability of two boys given the evidence.
+    pub fn two_boys_probability(&self) -> f64 {
+        match self {
+            TwoChildEvidence::AtLeastOneBoy => 1. / 3.,
+            TwoChildEvidence::OlderIsBoy | TwoChildEvidence::MetBoy => 1. / 2.,
+            TwoChildEvidence::BoyBornOnTuesday => 13. / 27.,
+        }
+    }
+}
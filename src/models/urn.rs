@@ -0,0 +1,343 @@
+//! Urn processes whose composition changes with every draw.
+
+use crate::{
+    select, Condition, Event, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+/// Color of the drawn ball.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrnEvent {
+    Red,
+    Black,
+}
+
+impl Event for UrnEvent {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrnOutcome {
+    /// Composition after the last draw.
+    pub red: usize,
+    pub black: usize,
+    /// Number of draws that were red.
+    pub red_draws: usize,
+}
+
+impl Outcome for UrnOutcome {}
+
+/// Urn where each drawn ball is put back together with `same` balls of its color and `opposite`
+/// balls of the other color.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UrnCondition {
+    red: usize,
+    black: usize,
+    same: usize,
+    opposite: usize,
+    draws: usize,
+    red_draws: usize,
+    /// Number of draws.
+    n: usize,
+}
+
+impl UrnCondition {
+    pub fn new(red: usize, black: usize, same: usize, opposite: usize, n: usize) -> Self {
+        assert!(red + black > 0);
+        Self {
+            red,
+            black,
+            same,
+            opposite,
+            draws: 0,
+            red_draws: 0,
+            n,
+        }
+    }
+}
+
+impl Condition for UrnCondition {
+    type Event = UrnEvent;
+    type Outcome = UrnOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        self.draws += 1;
+        match event {
+            UrnEvent::Red => {
+                self.red_draws += 1;
+                self.red += self.same;
+                self.black += self.opposite;
+            }
+            UrnEvent::Black => {
+                self.black += self.same;
+                self.red += self.opposite;
+            }
+        }
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        assert!(self.draws <= self.n);
+        if self.draws == self.n {
+            Some(UrnOutcome {
+                red: self.red,
+                black: self.black,
+                red_draws: self.red_draws,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl EventSpace for UrnCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        let p = self.red as f64 / (self.red + self.black) as f64;
+        vec![(p, UrnEvent::Red), (1.0 - p, UrnEvent::Black)]
+    }
+}
+
+/// Pólya urn: each drawn ball is returned with `added` more of its color.
+pub struct PolyaStartCondition {
+    pub red: usize,
+    pub black: usize,
+    pub added: usize,
+    pub n: usize,
+}
+
+impl StartCondition for PolyaStartCondition {
+    type Event = UrnEvent;
+    type Outcome = UrnOutcome;
+    type Condition = UrnCondition;
+
+    fn build(&self) -> Self::Condition {
+        UrnCondition::new(self.red, self.black, self.added, 0, self.n)
+    }
+}
+
+/// Friedman urn: each drawn ball is returned with `same` more of its color and `opposite` of the
+/// other color.
+pub struct FriedmanStartCondition {
+    pub red: usize,
+    pub black: usize,
+    pub same: usize,
+    pub opposite: usize,
+    pub n: usize,
+}
+
+impl StartCondition for FriedmanStartCondition {
+    type Event = UrnEvent;
+    type Outcome = UrnOutcome;
+    type Condition = UrnCondition;
+
+    fn build(&self) -> Self::Condition {
+        UrnCondition::new(self.red, self.black, self.same, self.opposite, self.n)
+    }
+}
+
+/// Number of red balls after the last draw.
+pub struct UrnRedRandomVariable {
+    /// Largest tabulated number of red balls.
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for UrnRedRandomVariable {
+    type Outcome = UrnOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.red
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// Number of red draws.
+pub struct UrnRedDrawsRandomVariable {
+    pub n: usize,
+}
+
+impl NonnegativeRandomVariable for UrnRedDrawsRandomVariable {
+    type Outcome = UrnOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.red_draws
+    }
+
+    fn space_len(&self) -> usize {
+        self.n + 1
+    }
+}
+
+/// Distribution of the number of red draws in `n` draws from a Pólya urn,
+/// $\text{BetaBinomial}(n, \text{red} / \text{added}, \text{black} / \text{added})$.
+pub fn polya_red_draws(
+    red: usize,
+    black: usize,
+    added: usize,
+    n: usize,
+) -> crate::analytic::BetaBinomial {
+    assert!(added > 0 && red > 0 && black > 0);
+    crate::analytic::BetaBinomial {
+        n,
+        alpha: red as f64 / added as f64,
+        beta: black as f64 / added as f64,
+    }
+}
+
+/// Expected number of red balls in a Friedman urn after `n` draws.
+///
+/// The total grows deterministically by `same + opposite` per draw, so
+/// $E[R_{k+1}] = E[R_k] (1 + \frac{\text{same} - \text{opposite}}{T_k}) + \text{opposite}$.
+pub fn friedman_expected_red(
+    red: usize,
+    black: usize,
+    same: usize,
+    opposite: usize,
+    n: usize,
+) -> f64 {
+    let mut expected = red as f64;
+    for k in 0..n {
+        let total = (red + black + k * (same + opposite)) as f64;
+        expected = expected * (1.0 + (same as f64 - opposite as f64) / total) + opposite as f64;
+    }
+    expected
+}
+
+/// The ball that is moved to the other urn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EhrenfestEvent {
+    FromA,
+    FromB,
+    /// Nothing moves; only happens in the lazy variant.
+    Stay,
+}
+
+impl Event for EhrenfestEvent {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EhrenfestOutcome {
+    /// Balls in urn A after the last step.
+    pub in_a: usize,
+}
+
+impl Outcome for EhrenfestOutcome {}
+
+/// Ehrenfest urns: each step moves a uniformly chosen ball to the other urn.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EhrenfestCondition {
+    in_a: usize,
+    total: usize,
+    lazy: bool,
+    steps: usize,
+    /// Number of steps.
+    n: usize,
+}
+
+impl EhrenfestCondition {
+    pub fn new(in_a: usize, total: usize, lazy: bool, n: usize) -> Self {
+        assert!(in_a <= total && total > 0);
+        Self {
+            in_a,
+            total,
+            lazy,
+            steps: 0,
+            n,
+        }
+    }
+}
+
+impl Condition for EhrenfestCondition {
+    type Event = EhrenfestEvent;
+    type Outcome = EhrenfestOutcome;
+
+    fn push(&mut self, event: Self::Event) {
+        self.steps += 1;
+        match event {
+            EhrenfestEvent::FromA => self.in_a -= 1,
+            EhrenfestEvent::FromB => self.in_a += 1,
+            EhrenfestEvent::Stay => (),
+        }
+    }
+
+    fn select_event(&self) -> Self::Event {
+        select(&self.event_space()).clone()
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        assert!(self.steps <= self.n);
+        if self.steps == self.n {
+            Some(EhrenfestOutcome { in_a: self.in_a })
+        } else {
+            None
+        }
+    }
+}
+
+impl EventSpace for EhrenfestCondition {
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        let scale = if self.lazy { 0.5 } else { 1.0 };
+        let from_a = self.in_a as f64 / self.total as f64 * scale;
+        let from_b = scale - from_a;
+        let mut space = vec![
+            (from_a, EhrenfestEvent::FromA),
+            (from_b, EhrenfestEvent::FromB),
+        ];
+        if self.lazy {
+            space.push((0.5, EhrenfestEvent::Stay));
+        }
+        space
+    }
+}
+
+pub struct EhrenfestStartCondition {
+    pub in_a: usize,
+    pub total: usize,
+    /// Leave the urns unchanged with probability 1/2 per step, which makes the chain aperiodic
+    /// so that it converges to the stationary distribution.
+    pub lazy: bool,
+    pub n: usize,
+}
+
+impl StartCondition for EhrenfestStartCondition {
+    type Event = EhrenfestEvent;
+    type Outcome = EhrenfestOutcome;
+    type Condition = EhrenfestCondition;
+
+    fn build(&self) -> Self::Condition {
+        EhrenfestCondition::new(self.in_a, self.total, self.lazy, self.n)
+    }
+}
+
+/// Number of balls in urn A.
+pub struct EhrenfestRandomVariable {
+    pub total: usize,
+}
+
+impl NonnegativeRandomVariable for EhrenfestRandomVariable {
+    type Outcome = EhrenfestOutcome;
+
+    fn map(&self, outcome: Self::Outcome) -> usize {
+        outcome.in_a
+    }
+
+    fn space_len(&self) -> usize {
+        self.total + 1
+    }
+}
+
+/// Stationary distribution of the number of balls in urn A.
+pub fn ehrenfest_stationary(total: usize) -> crate::analytic::Binomial {
+    crate::analytic::Binomial { n: total, p: 0.5 }
+}
+
+/// Expected number of balls in urn A after `n` steps.
+///
+/// The distance to the stationary mean shrinks by a factor of $1 - 2 / N$ per step, or
+/// $1 - 1 / N$ in the lazy variant.
+pub fn ehrenfest_expected_in_a(in_a: usize, total: usize, lazy: bool, n: usize) -> f64 {
+    let half = total as f64 / 2.0;
+    let rate = if lazy { 1.0 } else { 2.0 } / total as f64;
+    half + (in_a as f64 - half) * (1.0 - rate).powi(n as i32)
+}
@@ -0,0 +1,349 @@
+//! Probability mass functions over a range of nonnegative integers.
+
+use std::ops::RangeInclusive;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Probability mass function supported on `offset..offset + probs.len()`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pmf {
+    offset: usize,
+    probs: Vec<f64>,
+}
+
+impl Pmf {
+    pub fn new(offset: usize, probs: Vec<f64>) -> Self {
+        assert!(probs.iter().all(|p| p.is_finite() && *p >= 0.0));
+        Self { offset, probs }
+    }
+
+    /// Estimate the PMF from the counts returned by [`crate::sample_repeat`].
+    pub fn from_mass(mass: &[usize], rounds: usize) -> Self {
+        Self::new(0, crate::prob_mass_func(mass, rounds))
+    }
+
+    /// Distribution of a constant.
+    pub fn point(x: usize) -> Self {
+        Self::new(x, vec![1.0])
+    }
+
+    /// Smallest value of the stored support.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Probabilities of the values in [`Pmf::support`].
+    pub fn probs(&self) -> &[f64] {
+        &self.probs
+    }
+
+    pub fn support(&self) -> RangeInclusive<usize> {
+        self.offset..=self.offset + self.probs.len().saturating_sub(1)
+    }
+
+    /// Probabilities of all values from zero, as used by [`crate::expectation`].
+    pub fn to_vec(&self) -> Vec<f64> {
+        let mut v = vec![0.0; self.offset];
+        v.extend_from_slice(&self.probs);
+        v
+    }
+
+    pub fn prob(&self, x: usize) -> f64 {
+        x.checked_sub(self.offset)
+            .and_then(|i| self.probs.get(i))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    pub fn total(&self) -> f64 {
+        self.probs.iter().sum()
+    }
+
+    pub fn is_normalized(&self, tolerance: f64) -> bool {
+        (self.total() - 1.0).abs() <= tolerance
+    }
+
+    /// Scale the probabilities to sum to one.
+    pub fn normalize(&self) -> Self {
+        let total = self.total();
+        assert!(total > 0.0);
+        Self::new(self.offset, self.probs.iter().map(|p| p / total).collect())
+    }
+
+    /// $P(X \le x)$
+    pub fn cdf(&self, x: usize) -> f64 {
+        match x.checked_sub(self.offset) {
+            Some(i) => self.probs.iter().take(i + 1).sum(),
+            None => 0.0,
+        }
+    }
+
+    /// $P(X > x)$
+    pub fn survival(&self, x: usize) -> f64 {
+        match x.checked_sub(self.offset) {
+            Some(i) => self.probs.iter().skip(i + 1).sum(),
+            None => self.total(),
+        }
+    }
+
+    /// Smallest value whose CDF reaches `q`.
+    ///
+    /// Return `None` if the stored mass never reaches `q`.
+    pub fn quantile(&self, q: f64) -> Option<usize> {
+        let mut sum = 0.0;
+        for (i, p) in self.probs.iter().enumerate() {
+            sum += p;
+            if sum >= q {
+                return Some(self.offset + i);
+            }
+        }
+        None
+    }
+
+    /// Most likely value; the smallest one on ties.
+    pub fn mode(&self) -> usize {
+        let mut mode = 0;
+        for (i, &p) in self.probs.iter().enumerate() {
+            if p > self.probs[mode] {
+                mode = i;
+            }
+        }
+        self.offset + mode
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.raw_moment(1)
+    }
+
+    pub fn variance(&self) -> f64 {
+        self.central_moment(2)
+    }
+
+    /// Distribution of the sum of two independent random variables.
+    pub fn convolve(&self, other: &Self) -> Self {
+        if self.probs.is_empty() || other.probs.is_empty() {
+            return Self::new(self.offset + other.offset, Vec::new());
+        }
+        let mut probs = vec![0.0; self.probs.len() + other.probs.len() - 1];
+        for (i, p) in self.probs.iter().enumerate() {
+            for (j, q) in other.probs.iter().enumerate() {
+                probs[i + j] += p * q;
+            }
+        }
+        Self::new(self.offset + other.offset, probs)
+    }
+
+    /// Distribution that picks each component with the given weight.
+    ///
+    /// The weights are expected to sum to one.
+    pub fn mixture(components: &[(f64, &Self)]) -> Self {
+        let offset = components
+            .iter()
+            .map(|(_, pmf)| pmf.offset)
+            .min()
+            .unwrap_or(0);
+        let end = components
+            .iter()
+            .map(|(_, pmf)| pmf.offset + pmf.probs.len())
+            .max()
+            .unwrap_or(0);
+        let mut probs = vec![0.0; end.saturating_sub(offset)];
+        for (w, pmf) in components {
+            for (x, p) in pmf.values() {
+                probs[x - offset] += w * p;
+            }
+        }
+        Self::new(offset, probs)
+    }
+
+    /// Distribution of $kX$.
+    pub fn scale(&self, k: usize) -> Self {
+        if k == 0 {
+            return Self::new(0, vec![self.total()]);
+        }
+        let mut probs = vec![0.0; (self.probs.len().saturating_sub(1)) * k + 1];
+        for (i, &p) in self.probs.iter().enumerate() {
+            probs[i * k] = p;
+        }
+        Self::new(self.offset * k, probs)
+    }
+
+    /// Distribution of $X + k$.
+    pub fn shift(&self, k: usize) -> Self {
+        Self::new(self.offset + k, self.probs.clone())
+    }
+
+    /// Drop the mass outside of `range` without renormalizing.
+    pub fn truncate(&self, range: RangeInclusive<usize>) -> Self {
+        let start = (*range.start()).max(self.offset);
+        let probs = (start..=*range.end())
+            .take_while(|&x| x < self.offset + self.probs.len())
+            .map(|x| self.prob(x))
+            .collect();
+        Self::new(start, probs)
+    }
+
+    /// Distribution of $X$ given that $X$ satisfies `event`.
+    ///
+    /// Return `None` if the event has probability zero.
+    pub fn condition(&self, event: impl Fn(usize) -> bool) -> Option<Self> {
+        let probs: Vec<f64> = self
+            .values()
+            .map(|(x, p)| if event(x) { p } else { 0.0 })
+            .collect();
+        let total: f64 = probs.iter().sum();
+        if total == 0.0 {
+            return None;
+        }
+        Some(Self::new(
+            self.offset,
+            probs.into_iter().map(|p| p / total).collect(),
+        ))
+    }
+
+    /// $\frac{1}{2} \sum_x |p(x) - q(x)|$
+    pub fn total_variation(&self, other: &Self) -> f64 {
+        self.union_support(other)
+            .map(|x| (self.prob(x) - other.prob(x)).abs())
+            .sum::<f64>()
+            / 2.0
+    }
+
+    /// $\sum_x p(x) \ln \frac{p(x)}{q(x)}$
+    ///
+    /// Infinite if `other` misses mass of `self`.
+    pub fn kl_divergence(&self, other: &Self) -> f64 {
+        self.values()
+            .filter(|(_, p)| *p > 0.0)
+            .map(|(x, p)| p * (p / other.prob(x)).ln())
+            .sum()
+    }
+
+    /// $\sqrt{1 - \sum_x \sqrt{p(x) q(x)}}$
+    pub fn hellinger(&self, other: &Self) -> f64 {
+        let bhattacharyya: f64 = self.values().map(|(x, p)| (p * other.prob(x)).sqrt()).sum();
+        (1.0 - bhattacharyya).max(0.0).sqrt()
+    }
+
+    /// Values of the stored support with their probabilities.
+    pub fn values(&self) -> impl Iterator<Item = (usize, f64)> + '_ {
+        self.probs
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| (self.offset + i, p))
+    }
+
+    fn union_support(&self, other: &Self) -> std::ops::Range<usize> {
+        let start = self.offset.min(other.offset);
+        let end = (self.offset + self.probs.len()).max(other.offset + other.probs.len());
+        start..end
+    }
+}
+
+/// Moments, entropy and generating functions.
+///
+/// The `*_standard_error` methods treat the PMF as estimated from `rounds` independent samples
+/// and return the delta-method standard error of the corresponding plug-in estimate.
+impl Pmf {
+    /// $E[X^k]$
+    pub fn raw_moment(&self, k: u32) -> f64 {
+        self.expect(|x| (x as f64).powi(k as i32))
+    }
+
+    /// $E[(X - \mu)^k]$
+    pub fn central_moment(&self, k: u32) -> f64 {
+        let mean = self.mean();
+        self.expect(|x| (x as f64 - mean).powi(k as i32))
+    }
+
+    /// $E[X (X - 1) \cdots (X - k + 1)]$
+    pub fn factorial_moment(&self, k: u32) -> f64 {
+        self.expect(|x| falling_factorial(x, k))
+    }
+
+    /// $E[(X - \mu)^3] / \sigma^3$
+    pub fn skewness(&self) -> f64 {
+        self.central_moment(3) / self.variance().powf(1.5)
+    }
+
+    /// $E[(X - \mu)^4] / \sigma^4 - 3$
+    pub fn excess_kurtosis(&self) -> f64 {
+        self.central_moment(4) / self.variance().powi(2) - 3.0
+    }
+
+    /// Shannon entropy in nats.
+    pub fn entropy(&self) -> f64 {
+        -self
+            .probs
+            .iter()
+            .filter(|&&p| p > 0.0)
+            .map(|p| p * p.ln())
+            .sum::<f64>()
+    }
+
+    /// Probability generating function $G(z) = E[z^X]$.
+    pub fn pgf(&self, z: f64) -> f64 {
+        self.expect(|x| z.powi(x as i32))
+    }
+
+    /// Moment generating function $M(t) = E[e^{tX}]$.
+    pub fn mgf(&self, t: f64) -> f64 {
+        self.expect(|x| (t * x as f64).exp())
+    }
+
+    pub fn mean_standard_error(&self, rounds: usize) -> f64 {
+        self.raw_moment_standard_error(1, rounds)
+    }
+
+    pub fn variance_standard_error(&self, rounds: usize) -> f64 {
+        standard_error(self.central_moment(4) - self.variance().powi(2), rounds)
+    }
+
+    pub fn raw_moment_standard_error(&self, k: u32, rounds: usize) -> f64 {
+        standard_error(self.raw_moment(2 * k) - self.raw_moment(k).powi(2), rounds)
+    }
+
+    pub fn factorial_moment_standard_error(&self, k: u32, rounds: usize) -> f64 {
+        let second = self.expect(|x| falling_factorial(x, k).powi(2));
+        standard_error(second - self.factorial_moment(k).powi(2), rounds)
+    }
+
+    pub fn entropy_standard_error(&self, rounds: usize) -> f64 {
+        let second: f64 = self
+            .probs
+            .iter()
+            .filter(|&&p| p > 0.0)
+            .map(|p| p * p.ln().powi(2))
+            .sum();
+        standard_error(second - self.entropy().powi(2), rounds)
+    }
+
+    pub fn pgf_standard_error(&self, z: f64, rounds: usize) -> f64 {
+        standard_error(self.pgf(z * z) - self.pgf(z).powi(2), rounds)
+    }
+
+    pub fn mgf_standard_error(&self, t: f64, rounds: usize) -> f64 {
+        standard_error(self.mgf(2.0 * t) - self.mgf(t).powi(2), rounds)
+    }
+
+    /// $E[f(X)]$
+    fn expect(&self, f: impl Fn(usize) -> f64) -> f64 {
+        self.values().map(|(x, p)| f(x) * p).sum()
+    }
+}
+
+fn falling_factorial(x: usize, k: u32) -> f64 {
+    (0..k as usize).map(|i| x as f64 - i as f64).product()
+}
+
+fn standard_error(variance: f64, rounds: usize) -> f64 {
+    (variance.max(0.0) / rounds as f64).sqrt()
+}
+
+impl From<Vec<f64>> for Pmf {
+    fn from(probs: Vec<f64>) -> Self {
+        Self::new(0, probs)
+    }
+}
@@ -0,0 +1,236 @@
+//! Conditions with choice points that are resolved by a decision policy instead of chance.
+
+use std::hash::{Hash, Hasher};
+
+use rand::Rng;
+
+use crate::stats::{normal_interval, ConfidenceInterval};
+use crate::{
+    rng, sample, select, Condition, EventSpace, NonnegativeRandomVariable, Outcome, StartCondition,
+};
+
+/// Condition in which a decision maker picks some of the events.
+pub trait DecisionCondition: Condition {
+    /// Return the events the decision maker may choose from, or `None` if chance picks the next
+    /// event.
+    fn choices(&self) -> Option<Vec<Self::Event>>;
+}
+
+/// Rule for picking an event at a choice point.
+pub trait Policy<C: DecisionCondition> {
+    /// Return the probability of picking each of the choices.
+    ///
+    /// A deterministic policy puts all mass on one choice.
+    fn probabilities(&self, cond: &C, choices: &[C::Event]) -> Vec<f64>;
+}
+
+/// Policy that picks the choice at an index.
+pub fn deterministic(index: usize, len: usize) -> Vec<f64> {
+    let mut probs = vec![0.0; len];
+    probs[index] = 1.0;
+    probs
+}
+
+/// Condition whose choice points are resolved by a policy.
+#[derive(Debug, Clone)]
+pub struct Controlled<C, P> {
+    pub condition: C,
+    pub policy: P,
+}
+
+impl<C, P> Controlled<C, P>
+where
+    C: DecisionCondition,
+    P: Policy<C>,
+{
+    pub fn new(condition: C, policy: P) -> Self {
+        Self { condition, policy }
+    }
+
+    fn policy_space(&self, choices: Vec<C::Event>) -> Vec<(f64, C::Event)> {
+        let probs = self.policy.probabilities(&self.condition, &choices);
+        assert_eq!(probs.len(), choices.len());
+        probs.into_iter().zip(choices).collect()
+    }
+}
+
+impl<C: PartialEq, P> PartialEq for Controlled<C, P> {
+    /// Compare the conditions, assuming both are controlled by the same policy.
+    fn eq(&self, other: &Self) -> bool {
+        self.condition == other.condition
+    }
+}
+
+impl<C: Eq, P> Eq for Controlled<C, P> {}
+
+impl<C: Hash, P> Hash for Controlled<C, P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.condition.hash(state);
+    }
+}
+
+impl<C, P> Condition for Controlled<C, P>
+where
+    C: DecisionCondition,
+    C::Event: Clone,
+    P: Policy<C>,
+{
+    type Event = C::Event;
+    type Outcome = C::Outcome;
+
+    fn push(&mut self, event: Self::Event) {
+        self.condition.push(event);
+    }
+
+    fn select_event(&self) -> Self::Event {
+        match self.condition.choices() {
+            Some(choices) => select(&self.policy_space(choices)).clone(),
+            None => self.condition.select_event(),
+        }
+    }
+
+    fn outcome(&self) -> Option<Self::Outcome> {
+        self.condition.outcome()
+    }
+}
+
+impl<C, P> EventSpace for Controlled<C, P>
+where
+    C: DecisionCondition + EventSpace,
+    C::Event: Clone,
+    P: Policy<C>,
+{
+    fn event_space(&self) -> Vec<(f64, Self::Event)> {
+        match self.condition.choices() {
+            Some(choices) => self.policy_space(choices),
+            None => self.condition.event_space(),
+        }
+    }
+}
+
+pub struct ControlledStartCondition<S, P> {
+    pub start: S,
+    pub policy: P,
+}
+
+impl<S, P> StartCondition for ControlledStartCondition<S, P>
+where
+    S: StartCondition,
+    S::Condition: DecisionCondition,
+    S::Event: Clone,
+    P: Policy<S::Condition> + Clone,
+{
+    type Event = S::Event;
+    type Outcome = S::Outcome;
+    type Condition = Controlled<S::Condition, P>;
+
+    fn build(&self) -> Self::Condition {
+        Controlled::new(self.start.build(), self.policy.clone())
+    }
+}
+
+/// Result of running several policies on the same random numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicyComparison {
+    /// Number of rounds in which the random variable was tabulated under every policy.
+    pub rounds: usize,
+    /// Number of rounds left out because the random variable was not tabulated under some
+    /// policy.
+    pub skipped: usize,
+    /// Estimated expectation of the random variable under each policy.
+    pub means: Vec<f64>,
+    pub mean_intervals: Vec<ConfidenceInterval>,
+    /// Index of the policy with the highest estimated expectation.
+    pub best: usize,
+    /// Interval for the advantage of the best policy over each policy, from the paired
+    /// differences of the rounds.
+    pub differences: Vec<ConfidenceInterval>,
+}
+
+impl PolicyComparison {
+    /// Whether the best policy is significantly better than every other one.
+    pub fn is_significant(&self) -> bool {
+        self.differences
+            .iter()
+            .enumerate()
+            .all(|(i, ci)| i == self.best || ci.lower > 0.0)
+    }
+}
+
+/// Run every policy for `rounds` rounds with common random numbers and compare the expectation
+/// of a random variable, where higher is better.
+///
+/// Every round reseeds the generator of the current thread with the same seed for each policy,
+/// so the policies face the same chance events as far as their paths agree and the differences
+/// have a smaller variance than with independent runs. The seeds are drawn from the generator,
+/// so the comparison is reproducible after [`rng::seed`], and the generator is restored after
+/// every round, so the caller's stream only advances by the seeds.
+///
+/// Rounds in which the random variable maps the outcome of some policy outside its space are
+/// left out for all policies, which keeps the differences paired.
+pub fn compare_policies<S, P, RV, O>(
+    start: &S,
+    policies: &[P],
+    rounds: usize,
+    rv: &RV,
+    confidence: f64,
+) -> PolicyComparison
+where
+    O: Outcome,
+    S: StartCondition<Outcome = O>,
+    S::Condition: DecisionCondition,
+    S::Event: Clone,
+    P: Policy<S::Condition> + Clone,
+    RV: NonnegativeRandomVariable<Outcome = O>,
+{
+    assert!(!policies.is_empty() && rounds > 1);
+    let seeds: Vec<u64> = rng::with_rng(|rng| (0..rounds).map(|_| rng.gen()).collect());
+    let mut values: Vec<Vec<f64>> = vec![Vec::with_capacity(rounds); policies.len()];
+    for seed in seeds {
+        let round: Vec<usize> = policies
+            .iter()
+            .map(|policy| {
+                rng::with_seed(seed, || {
+                    rv.map(sample(Controlled::new(start.build(), policy.clone())))
+                })
+            })
+            .collect();
+        if round.iter().all(|&v| v < rv.space_len()) {
+            for (xs, v) in values.iter_mut().zip(round) {
+                xs.push(v as f64);
+            }
+        }
+    }
+    let tabulated = values[0].len();
+    assert!(tabulated > 1, "fewer than two rounds were tabulated");
+
+    let n = tabulated as f64;
+    let mean_and_error = |xs: &[f64]| {
+        let mean = xs.iter().sum::<f64>() / n;
+        let variance = xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        (mean, (variance / n).sqrt())
+    };
+    let summaries: Vec<(f64, f64)> = values.iter().map(|xs| mean_and_error(xs)).collect();
+    let best = (0..policies.len())
+        .max_by(|&i, &j| summaries[i].0.total_cmp(&summaries[j].0))
+        .unwrap();
+    let differences = values
+        .iter()
+        .map(|xs| {
+            let paired: Vec<f64> = values[best].iter().zip(xs).map(|(b, x)| b - x).collect();
+            let (mean, error) = mean_and_error(&paired);
+            normal_interval(mean, error, confidence)
+        })
+        .collect();
+    PolicyComparison {
+        rounds: tabulated,
+        skipped: rounds - tabulated,
+        means: summaries.iter().map(|&(mean, _)| mean).collect(),
+        mean_intervals: summaries
+            .iter()
+            .map(|&(mean, error)| normal_interval(mean, error, confidence))
+            .collect(),
+        best,
+        differences,
+    }
+}
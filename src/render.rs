@@ -0,0 +1,172 @@
+//! Drawing of probability mass functions.
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use crate::stats::ConfidenceInterval;
+
+/// Characters used to draw a bar chart in the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+    Ascii,
+    /// Block elements with eighth-cell resolution.
+    Unicode,
+}
+
+impl Charset {
+    fn reference_marker(&self) -> char {
+        match self {
+            Charset::Ascii => '|',
+            Charset::Unicode => '┃',
+        }
+    }
+
+    /// Draw a bar of `eighths / 8` cells.
+    fn bar(&self, eighths: usize) -> Vec<char> {
+        match self {
+            Charset::Ascii => vec!['#'; (eighths + 4) / 8],
+            Charset::Unicode => {
+                const PARTIAL: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+                let mut bar = vec!['█'; eighths / 8];
+                if !eighths.is_multiple_of(8) {
+                    bar.push(PARTIAL[eighths % 8]);
+                }
+                bar
+            }
+        }
+    }
+}
+
+/// Draw a horizontal bar per value of the random variable.
+///
+/// The longest bar spans `width` cells. When a reference PMF is given, its probability is marked
+/// on each row and printed next to the simulated one.
+pub fn bar_chart(pmf: &[f64], reference: Option<&[f64]>, width: usize, charset: Charset) -> String {
+    let max = pmf
+        .iter()
+        .chain(reference.unwrap_or(&[]))
+        .fold(0.0_f64, |a, &b| a.max(b));
+    let len = pmf.len().max(reference.map_or(0, |r| r.len()));
+    let label_width = len.saturating_sub(1).to_string().len();
+    let scale = |p: f64| {
+        if max > 0.0 {
+            p / max * width as f64
+        } else {
+            0.0
+        }
+    };
+
+    let mut chart = String::new();
+    for x in 0..len {
+        let p = pmf.get(x).copied().unwrap_or(0.0);
+        let mut row = charset.bar((scale(p) * 8.0).round() as usize);
+        row.resize(width + 1, ' ');
+        let r = reference.map(|r| r.get(x).copied().unwrap_or(0.0));
+        if let Some(r) = r {
+            let column = (scale(r).round() as usize).min(width);
+            row[column] = charset.reference_marker();
+        }
+        let row: String = row.into_iter().collect();
+        write!(chart, "{x:>label_width$} {row} {p:.4}").unwrap();
+        if let Some(r) = r {
+            write!(chart, " ({r:.4})").unwrap();
+        }
+        chart.push('\n');
+    }
+    chart
+}
+
+const SVG_WIDTH: f64 = 640.0;
+const SVG_HEIGHT: f64 = 400.0;
+const MARGIN: f64 = 40.0;
+
+/// Write a self-contained SVG bar chart.
+///
+/// A reference PMF is drawn as a marker over each bar and confidence intervals as error bars.
+pub fn write_svg<W: Write>(
+    mut writer: W,
+    pmf: &[f64],
+    reference: Option<&[f64]>,
+    intervals: Option<&[ConfidenceInterval]>,
+) -> io::Result<()> {
+    let max = pmf
+        .iter()
+        .chain(reference.unwrap_or(&[]))
+        .chain(intervals.unwrap_or(&[]).iter().map(|ci| &ci.upper))
+        .fold(0.0_f64, |a, &b| a.max(b));
+    let max = if max > 0.0 { max } else { 1.0 };
+    let len = pmf.len().max(reference.map_or(0, |r| r.len())).max(1);
+    let plot_width = SVG_WIDTH - 2.0 * MARGIN;
+    let plot_height = SVG_HEIGHT - 2.0 * MARGIN;
+    let slot = plot_width / len as f64;
+    let x_of = |x: usize| MARGIN + slot * x as f64;
+    let y_of = |p: f64| SVG_HEIGHT - MARGIN - p.clamp(0.0, max) / max * plot_height;
+
+    writeln!(
+        writer,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{SVG_WIDTH}" height="{SVG_HEIGHT}" viewBox="0 0 {SVG_WIDTH} {SVG_HEIGHT}" font-family="sans-serif" font-size="10">"#
+    )?;
+    writeln!(
+        writer,
+        r#"<rect width="{SVG_WIDTH}" height="{SVG_HEIGHT}" fill="white"/>"#
+    )?;
+
+    // Axes with ticks at 0, half and the maximum probability.
+    let bottom = SVG_HEIGHT - MARGIN;
+    writeln!(
+        writer,
+        r#"<path d="M{MARGIN} {MARGIN} V{bottom} H{}" fill="none" stroke="black"/>"#,
+        SVG_WIDTH - MARGIN
+    )?;
+    for p in [0.0, max / 2.0, max] {
+        let y = y_of(p);
+        writeln!(
+            writer,
+            r#"<text x="{}" y="{y}" text-anchor="end" dominant-baseline="middle">{p:.3}</text>"#,
+            MARGIN - 4.0
+        )?;
+    }
+    let label_every = len.div_ceil(20);
+    for x in (0..len).step_by(label_every) {
+        writeln!(
+            writer,
+            r#"<text x="{}" y="{}" text-anchor="middle">{x}</text>"#,
+            x_of(x) + slot / 2.0,
+            bottom + 14.0
+        )?;
+    }
+
+    for (x, &p) in pmf.iter().enumerate() {
+        let y = y_of(p);
+        writeln!(
+            writer,
+            r##"<rect x="{}" y="{y}" width="{}" height="{}" fill="#4c78a8"/>"##,
+            x_of(x) + slot * 0.1,
+            slot * 0.8,
+            bottom - y
+        )?;
+    }
+    for (x, ci) in intervals.unwrap_or(&[]).iter().enumerate() {
+        let center = x_of(x) + slot / 2.0;
+        let cap = slot * 0.15;
+        let (top, low) = (y_of(ci.upper), y_of(ci.lower));
+        writeln!(
+            writer,
+            r#"<path d="M{center} {top} V{low} M{} {top} h{} M{} {low} h{}" fill="none" stroke="black"/>"#,
+            center - cap,
+            2.0 * cap,
+            center - cap,
+            2.0 * cap
+        )?;
+    }
+    for (x, &r) in reference.unwrap_or(&[]).iter().enumerate() {
+        writeln!(
+            writer,
+            r##"<path d="M{} {} h{}" fill="none" stroke="#e45756" stroke-width="2"/>"##,
+            x_of(x),
+            y_of(r),
+            slot
+        )?;
+    }
+    writeln!(writer, "</svg>")
+}
@@ -0,0 +1,111 @@
+//! Summary of a simulation run that can be exported for further analysis.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::stats::{mean_interval, wilson_interval, ConfidenceInterval};
+use crate::{expectation, prob_mass_func, variance};
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SimulationReport {
+    pub model: String,
+    pub parameters: BTreeMap<String, f64>,
+    /// Seed the generator was reset to before the run, if any.
+    pub seed: Option<u64>,
+    pub rounds: usize,
+    /// Number of rounds in which the random variable took each value.
+    pub counts: Vec<usize>,
+    /// Number of rounds in which the random variable took a value outside of `counts`.
+    pub untabulated: usize,
+    pub pmf: Vec<f64>,
+    /// Mean of the random variable; `None` if some rounds are untabulated.
+    pub mean: Option<f64>,
+    /// Variance of the random variable; `None` if some rounds are untabulated.
+    pub variance: Option<f64>,
+    /// Confidence level of the intervals.
+    pub confidence: f64,
+    pub pmf_intervals: Vec<ConfidenceInterval>,
+    pub mean_interval: Option<ConfidenceInterval>,
+}
+
+impl SimulationReport {
+    /// Summarize the counts returned by [`crate::sample_repeat`].
+    ///
+    /// The rounds missing from `counts` are recorded as untabulated. The moments cannot be
+    /// estimated from a truncated PMF, so they are left out if there are any. Panics if `rounds`
+    /// is zero or smaller than the total of `counts`.
+    pub fn new(
+        model: impl Into<String>,
+        counts: Vec<usize>,
+        rounds: usize,
+        confidence: f64,
+    ) -> Self {
+        let tabulated: usize = counts.iter().sum();
+        assert!(
+            rounds > 0 && tabulated <= rounds,
+            "the counts must come from a positive number of rounds, not {tabulated} out of {rounds}"
+        );
+        let untabulated = rounds - tabulated;
+        let pmf = prob_mass_func(&counts, rounds);
+        let pmf_intervals = counts
+            .iter()
+            .map(|&count| wilson_interval(count, rounds, confidence))
+            .collect();
+        let (mean, variance, mean_interval) = if untabulated == 0 {
+            let mean = expectation(&pmf);
+            (
+                Some(mean),
+                Some(variance(&pmf, mean)),
+                Some(mean_interval(&pmf, rounds, confidence)),
+            )
+        } else {
+            (None, None, None)
+        };
+        Self {
+            model: model.into(),
+            parameters: BTreeMap::new(),
+            seed: None,
+            rounds,
+            counts,
+            untabulated,
+            pmf,
+            mean,
+            variance,
+            confidence,
+            pmf_intervals,
+            mean_interval,
+        }
+    }
+
+    /// Write one row per value of the random variable.
+    ///
+    /// The model, parameters, seed, rounds, untabulated rounds and confidence level come first as
+    /// comment lines starting with `#`, so that the file can be traced back to its run.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "# model: {}", self.model)?;
+        for (name, value) in &self.parameters {
+            writeln!(writer, "# {name} = {value}")?;
+        }
+        if let Some(seed) = self.seed {
+            writeln!(writer, "# seed: {seed}")?;
+        }
+        writeln!(writer, "# rounds: {}", self.rounds)?;
+        writeln!(writer, "# untabulated: {}", self.untabulated)?;
+        writeln!(writer, "# confidence: {}", self.confidence)?;
+        writeln!(writer, "value,count,probability,ci_lower,ci_upper")?;
+        let rows = self.counts.iter().zip(&self.pmf).zip(&self.pmf_intervals);
+        for (x, ((count, p), ci)) in rows.enumerate() {
+            writeln!(writer, "{},{},{},{},{}", x, count, p, ci.lower, ci.upper)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn write_json<W: Write>(&self, writer: W) -> io::Result<()> {
+        serde_json::to_writer_pretty(writer, self).map_err(io::Error::from)
+    }
+}
@@ -0,0 +1,43 @@
+//! Random number generator shared by the simulations on the current thread.
+
+use std::cell::RefCell;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+thread_local! {
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
+}
+
+/// Reseed the generator of the current thread so that the following simulations are
+/// reproducible.
+pub fn seed(seed: u64) {
+    RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed));
+}
+
+/// Run a closure with the generator of the current thread.
+pub fn with_rng<T>(f: impl FnOnce(&mut StdRng) -> T) -> T {
+    RNG.with(|rng| f(&mut rng.borrow_mut()))
+}
+
+/// Run a closure with the generator of the current thread reseeded, then restore the generator
+/// to its state before the call.
+///
+/// Lets a function use fixed seeds internally, such as for common random numbers, without
+/// leaving the caller's generator reseeded.
+pub fn with_seed<T>(seed: u64, f: impl FnOnce() -> T) -> T {
+    /// Puts the saved generator back, even if the closure panics.
+    struct Restore(Option<StdRng>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            if let Some(saved) = self.0.take() {
+                RNG.with(|rng| *rng.borrow_mut() = saved);
+            }
+        }
+    }
+
+    let saved = RNG.with(|rng| rng.replace(StdRng::seed_from_u64(seed)));
+    let _restore = Restore(Some(saved));
+    f()
+}
@@ -0,0 +1,113 @@
+//! Derivatives of expectations with respect to a parameter of the event probabilities.
+
+use rand::Rng;
+
+use crate::stats::{normal_interval, ConfidenceInterval};
+use crate::{rng, sample, Condition, EventSpace, Outcome, StartCondition};
+
+/// Estimate of a derivative from independent rounds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gradient {
+    pub estimate: f64,
+    pub standard_error: f64,
+    pub rounds: usize,
+}
+
+impl Gradient {
+    fn from_samples(samples: &[f64]) -> Self {
+        let n = samples.len() as f64;
+        let estimate = samples.iter().sum::<f64>() / n;
+        let variance = samples.iter().map(|x| (x - estimate).powi(2)).sum::<f64>() / (n - 1.0);
+        Self {
+            estimate,
+            standard_error: (variance / n).sqrt(),
+            rounds: samples.len(),
+        }
+    }
+
+    pub fn interval(&self, confidence: f64) -> ConfidenceInterval {
+        normal_interval(self.estimate, self.standard_error, confidence)
+    }
+}
+
+/// Score-function (likelihood-ratio) estimate of $\frac{d}{d\theta} E_\theta[f(O)]$.
+///
+/// Each round samples a path at `theta` and weights $f(O)$ with the score
+/// $\sum_t \frac{d}{d\theta} \ln p_\theta(e_t)$ of the events on the path. The derivative of each
+/// log-probability is a central difference of the probabilities that the conditions built at
+/// `theta - h` and `theta + h` give the same event, so the models need no derivative of their
+/// own. They are pushed the same events in lockstep and have to assign them positive
+/// probability.
+pub fn score_function<S, O>(
+    start: impl Fn(f64) -> S,
+    theta: f64,
+    h: f64,
+    rounds: usize,
+    f: impl Fn(O) -> f64,
+) -> Gradient
+where
+    O: Outcome,
+    S: StartCondition<Outcome = O>,
+    S::Condition: EventSpace,
+    S::Event: Clone + PartialEq,
+{
+    assert!(rounds > 1 && h > 0.0);
+    let (start, lower, upper) = (start(theta), start(theta - h), start(theta + h));
+    let prob = |cond: &S::Condition, event: &S::Event| {
+        cond.event_space()
+            .into_iter()
+            .filter(|(_, e)| e == event)
+            .map(|(p, _)| p)
+            .sum::<f64>()
+    };
+    let samples: Vec<f64> = (0..rounds)
+        .map(|_| {
+            let (mut cond, mut lower, mut upper) = (start.build(), lower.build(), upper.build());
+            let mut score = 0.0;
+            loop {
+                if let Some(outcome) = cond.outcome() {
+                    return f(outcome) * score;
+                }
+                let event = cond.select_event();
+                score += (prob(&upper, &event).ln() - prob(&lower, &event).ln()) / (2.0 * h);
+                lower.push(event.clone());
+                upper.push(event.clone());
+                cond.push(event);
+            }
+        })
+        .collect();
+    Gradient::from_samples(&samples)
+}
+
+/// Central finite-difference estimate of $\frac{d}{d\theta} E_\theta[f(O)]$ with common random
+/// numbers.
+///
+/// Every round runs the models built at `theta - h` and `theta + h` after reseeding the
+/// generator of the current thread with the same seed, so their outcomes are strongly correlated
+/// and the difference has a small variance. The estimate is biased by $O(h^2)$. The seeds are
+/// drawn from the generator, so the estimate is reproducible after [`rng::seed`], and the
+/// generator is restored after every run, so the caller's stream only advances by the seeds.
+pub fn finite_difference<S, O>(
+    start: impl Fn(f64) -> S,
+    theta: f64,
+    h: f64,
+    rounds: usize,
+    f: impl Fn(O) -> f64,
+) -> Gradient
+where
+    O: Outcome,
+    S: StartCondition<Outcome = O>,
+{
+    assert!(rounds > 1 && h > 0.0);
+    let (lower, upper) = (start(theta - h), start(theta + h));
+    let seeds: Vec<u64> = rng::with_rng(|rng| (0..rounds).map(|_| rng.gen()).collect());
+    let samples: Vec<f64> = seeds
+        .into_iter()
+        .map(|seed| {
+            let low = rng::with_seed(seed, || f(sample(lower.build())));
+            let high = rng::with_seed(seed, || f(sample(upper.build())));
+            (high - low) / (2.0 * h)
+        })
+        .collect();
+    Gradient::from_samples(&samples)
+}
@@ -0,0 +1,104 @@
+//! Confidence intervals for simulated estimates.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConfidenceInterval {
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl ConfidenceInterval {
+    pub fn contains(&self, x: f64) -> bool {
+        self.lower <= x && x <= self.upper
+    }
+}
+
+/// Quantile function of the standard normal distribution.
+///
+/// Uses Peter Acklam's rational approximation, accurate to about $10^{-9}$.
+pub fn normal_quantile(p: f64) -> f64 {
+    assert!(0.0 < p && p < 1.0);
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    }
+}
+
+/// Two-sided critical value of the standard normal distribution for a confidence level.
+fn z_score(confidence: f64) -> f64 {
+    normal_quantile(0.5 + confidence / 2.0)
+}
+
+/// Normal approximation interval around an estimate.
+pub fn normal_interval(estimate: f64, standard_error: f64, confidence: f64) -> ConfidenceInterval {
+    let half = z_score(confidence) * standard_error;
+    ConfidenceInterval {
+        lower: estimate - half,
+        upper: estimate + half,
+    }
+}
+
+/// Wilson score interval for a probability estimated from `count` hits in `rounds` trials.
+pub fn wilson_interval(count: usize, rounds: usize, confidence: f64) -> ConfidenceInterval {
+    let z = z_score(confidence);
+    let n = rounds as f64;
+    let p = count as f64 / n;
+    let denominator = 1.0 + z * z / n;
+    let center = (p + z * z / (2.0 * n)) / denominator;
+    let half = z * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt() / denominator;
+    ConfidenceInterval {
+        lower: center - half,
+        upper: center + half,
+    }
+}
+
+/// Interval for the expectation of a random variable from its simulated PMF.
+pub fn mean_interval(pmf: &[f64], rounds: usize, confidence: f64) -> ConfidenceInterval {
+    let mean = crate::expectation(pmf);
+    let standard_error = (crate::variance(pmf, mean) / rounds as f64).sqrt();
+    normal_interval(mean, standard_error, confidence)
+}
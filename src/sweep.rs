@@ -0,0 +1,188 @@
+//! Simulation of a model over a grid of parameter values.
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::report::SimulationReport;
+use crate::{rng, sample_repeat, NonnegativeRandomVariable, Outcome, StartCondition};
+
+/// Cartesian product of the values of named parameters.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Grid {
+    names: Vec<String>,
+    values: Vec<Vec<f64>>,
+}
+
+impl Grid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a parameter taking each of `values`.
+    pub fn axis(mut self, name: impl Into<String>, values: impl IntoIterator<Item = f64>) -> Self {
+        self.names.push(name.into());
+        self.values.push(values.into_iter().collect());
+        self
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Number of grid points.
+    pub fn len(&self) -> usize {
+        self.values.iter().map(Vec::len).product()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Parameter values of every grid point, with the last axis varying fastest.
+    pub fn points(&self) -> Vec<Vec<f64>> {
+        self.values.iter().fold(vec![Vec::new()], |points, axis| {
+            points
+                .iter()
+                .flat_map(|point| {
+                    axis.iter().map(move |&v| {
+                        let mut point = point.clone();
+                        point.push(v);
+                        point
+                    })
+                })
+                .collect()
+        })
+    }
+}
+
+/// Reports of all grid points of a sweep.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sweep {
+    /// Names of the parameters, in the order of the grid axes.
+    pub names: Vec<String>,
+    /// One report per grid point, in the order of [`Grid::points`].
+    pub reports: Vec<SimulationReport>,
+}
+
+impl Sweep {
+    /// Write one row per grid point with the estimated mean and its interval.
+    ///
+    /// The moments are left empty at grid points with untabulated rounds.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for name in &self.names {
+            write!(writer, "{},", name)?;
+        }
+        writeln!(
+            writer,
+            "seed,rounds,untabulated,mean,mean_ci_lower,mean_ci_upper,variance"
+        )?;
+        for report in &self.reports {
+            self.write_parameters(&mut writer, report)?;
+            write!(
+                writer,
+                "{},{},{},",
+                report.seed.unwrap_or_default(),
+                report.rounds,
+                report.untabulated
+            )?;
+            match (report.mean, report.mean_interval, report.variance) {
+                (Some(mean), Some(ci), Some(variance)) => {
+                    writeln!(writer, "{},{},{},{}", mean, ci.lower, ci.upper, variance)?
+                }
+                _ => writeln!(writer, ",,,")?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Write one row per grid point and value of the random variable.
+    pub fn write_pmf_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for name in &self.names {
+            write!(writer, "{},", name)?;
+        }
+        writeln!(writer, "value,count,probability,ci_lower,ci_upper")?;
+        for report in &self.reports {
+            let rows = report.counts.iter().zip(&report.pmf);
+            for (x, ((count, p), ci)) in rows.zip(&report.pmf_intervals).enumerate() {
+                self.write_parameters(&mut writer, report)?;
+                writeln!(writer, "{},{},{},{},{}", x, count, p, ci.lower, ci.upper)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_parameters<W: Write>(
+        &self,
+        writer: &mut W,
+        report: &SimulationReport,
+    ) -> io::Result<()> {
+        for name in &self.names {
+            write!(writer, "{},", report.parameters[name])?;
+        }
+        Ok(())
+    }
+}
+
+/// Run `rounds` rounds at every grid point, spread over the available cores.
+///
+/// `start` and `rv` build the model from the parameter values of a point, in the order of the
+/// grid axes. Point `i` reseeds the generator of its thread with `seed + i`, so the results do not
+/// depend on how the points are distributed over the threads.
+pub fn sweep<S, RV, O>(
+    model: &str,
+    grid: &Grid,
+    rounds: usize,
+    confidence: f64,
+    seed: u64,
+    start: impl Fn(&[f64]) -> S + Sync,
+    rv: impl Fn(&[f64]) -> RV + Sync,
+) -> Sweep
+where
+    O: Outcome,
+    S: StartCondition<Outcome = O>,
+    RV: NonnegativeRandomVariable<Outcome = O>,
+{
+    let points = grid.points();
+    let threads = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(points.len());
+    let next = AtomicUsize::new(0);
+    let run = |i: usize| {
+        let point = &points[i];
+        let point_seed = seed.wrapping_add(i as u64);
+        rng::seed(point_seed);
+        let counts = sample_repeat(start(point), rounds, rv(point));
+        let mut report = SimulationReport::new(model, counts, rounds, confidence);
+        report.parameters = grid.names.iter().cloned().zip(point.clone()).collect();
+        report.seed = Some(point_seed);
+        (i, report)
+    };
+
+    let mut reports: Vec<Option<SimulationReport>> = vec![None; points.len()];
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        if i >= points.len() {
+                            return done;
+                        }
+                        done.push(run(i));
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            for (i, report) in worker.join().unwrap() {
+                reports[i] = Some(report);
+            }
+        }
+    });
+    Sweep {
+        names: grid.names.clone(),
+        reports: reports.into_iter().map(Option::unwrap).collect(),
+    }
+}
@@ -0,0 +1,78 @@
+use cond_prob_sim::analytic::{
+    gambler_s_ruin_win, ln_choose, ln_gamma, Bernoulli, Binomial, Distribution, Geometric,
+    Hypergeometric, NegativeBinomial, Poisson,
+};
+
+#[test]
+fn ln_gamma_matches_factorials() {
+    let mut factorial = 1.0_f64;
+    for n in 1..20 {
+        factorial *= n as f64;
+        assert!((ln_gamma(n as f64 + 1.) - factorial.ln()).abs() < 1e-10);
+    }
+    assert!((ln_gamma(0.5) - std::f64::consts::PI.sqrt().ln()).abs() < 1e-12);
+    assert!((ln_choose(10, 3) - 120_f64.ln()).abs() < 1e-12);
+}
+
+#[test]
+fn moments_match_pmfs() {
+    let distributions: [(&str, Box<dyn Distribution>, usize); 6] = [
+        ("bernoulli", Box::new(Bernoulli { p: 0.3 }), 2),
+        ("binomial", Box::new(Binomial { n: 10_000, p: 0.3 }), 10_001),
+        ("geometric", Box::new(Geometric { p: 0.2 }), 400),
+        (
+            "negative binomial",
+            Box::new(NegativeBinomial { r: 50, p: 0.2 }),
+            2_000,
+        ),
+        (
+            "hypergeometric",
+            Box::new(Hypergeometric {
+                successes: 5_000,
+                failures: 45_000,
+                n: 1_000,
+            }),
+            1_001,
+        ),
+        ("poisson", Box::new(Poisson { lambda: 500. }), 2_000),
+    ];
+    for (name, distribution, len) in distributions {
+        let pmf = distribution.pmf(len);
+        println!(
+            "{name}: total = {}, mean = {}, variance = {}",
+            pmf.total(),
+            pmf.mean(),
+            pmf.variance()
+        );
+        assert!(pmf.is_normalized(1e-9));
+        assert!((pmf.mean() - distribution.mean()).abs() < 1e-6 * distribution.mean().max(1.));
+        assert!(
+            (pmf.variance() - distribution.variance()).abs()
+                < 1e-6 * distribution.variance().max(1.)
+        );
+    }
+}
+
+#[test]
+fn gambler_s_ruin() {
+    assert!((gambler_s_ruin_win(5, 100, 0.5) - 0.05).abs() < 1e-12);
+    let r: f64 = 0.6 / 0.4;
+    assert!((gambler_s_ruin_win(2, 4, 0.4) - (1. - r.powi(2)) / (1. - r.powi(4))).abs() < 1e-12);
+    // Would overflow if computed with powers of $q/p$ directly.
+    let p = gambler_s_ruin_win(1_000, 2_000, 0.4);
+    assert!((p.ln() - 1_000. * (0.4_f64 / 0.6).ln()).abs() < 1e-9);
+    assert!((gambler_s_ruin_win(1_999, 2_000, 0.4) - 2. / 3.).abs() < 1e-12);
+    assert_eq!(gambler_s_ruin_win(0, 0, 0.5), 0.);
+    assert_eq!(gambler_s_ruin_win(0, 4, 0.4), 0.);
+    assert_eq!(gambler_s_ruin_win(4, 4, 0.4), 1.);
+}
+
+#[test]
+fn hypergeometric_beyond_population() {
+    let distribution = Hypergeometric {
+        successes: 2,
+        failures: 2,
+        n: 6,
+    };
+    assert!((0..=6).all(|x| distribution.prob(x) == 0.));
+}
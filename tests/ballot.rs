@@ -0,0 +1,30 @@
+use cond_prob_sim::models::ballot::*;
+
+#[cfg(test)]
+mod tests {
+    use cond_prob_sim::exact::exact_prob_mass_func;
+    use cond_prob_sim::{prob_mass_func, sample_repeat};
+
+    use super::*;
+
+    #[test]
+    fn rounds_100_000_a_7_b_4() {
+        let rounds = 100_000;
+        let (a, b) = (7, 4);
+        let start = || BallotStartCondition { a, b };
+
+        let mass = sample_repeat(start(), rounds, BallotRandomVariable);
+        let p = prob_mass_func(&mass, rounds)[1];
+        println!("P(ahead) = {} ({})", p, ahead_probability(a, b));
+        assert!((p - ahead_probability(a, b)).abs() < 0.01);
+
+        let mass = sample_repeat(start(), rounds, BallotNeverBehindRandomVariable);
+        let p = prob_mass_func(&mass, rounds)[1];
+        assert!((p - never_behind_probability(a, b)).abs() < 0.01);
+
+        let exact = exact_prob_mass_func(start(), BallotRandomVariable, 0.);
+        assert!((exact[1] - ahead_probability(a, b)).abs() < 1e-12);
+        let exact = exact_prob_mass_func(start(), BallotNeverBehindRandomVariable, 0.);
+        assert!((exact[1] - never_behind_probability(a, b)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,33 @@
+use cond_prob_sim::models::banach_matchbox::*;
+
+#[cfg(test)]
+mod tests {
+    use cond_prob_sim::exact::exact_prob_mass_func;
+    use cond_prob_sim::pmf::Pmf;
+    use cond_prob_sim::sample_repeat;
+
+    use super::*;
+
+    #[test]
+    fn rounds_100_000_n_10() {
+        let rounds = 100_000;
+        let n = 10;
+        let mass = sample_repeat(
+            BanachStartCondition { n },
+            rounds,
+            BanachRandomVariable { n },
+        );
+        let simulated = Pmf::from_mass(&mass, rounds);
+        let reference = remaining_pmf(n);
+        println!("PMF: {:?}", simulated.probs());
+        assert!(reference.is_normalized(1e-12));
+        assert!(simulated.total_variation(&reference) < 0.01);
+
+        let exact = Pmf::from(exact_prob_mass_func(
+            BanachStartCondition { n },
+            BanachRandomVariable { n },
+            0.,
+        ));
+        assert!(exact.total_variation(&reference) < 1e-12);
+    }
+}
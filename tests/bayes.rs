@@ -0,0 +1,124 @@
+use cond_prob_sim::bayes::{exact_posterior, posterior};
+use cond_prob_sim::models::bertrand_box::*;
+use cond_prob_sim::models::diagnostic_test::*;
+use cond_prob_sim::models::two_child::*;
+
+#[test]
+fn diagnostic_test_rounds_1_000_000() {
+    let rounds = 1_000_000;
+    let (prevalence, sensitivity, specificity) = (0.01, 0.95, 0.9);
+    let start = |tests| DiagnosticStartCondition {
+        prevalence,
+        sensitivity,
+        specificity,
+        tests,
+    };
+
+    // A single positive result leaves the patient more likely healthy.
+    let expected = posterior_sick(prevalence, sensitivity, specificity, 1, 1);
+    assert!(expected < 0.1);
+    let estimate = posterior(start(1), rounds, DiagnosticSickRandomVariable, |o| {
+        o.positives == 1
+    });
+    let p = estimate.pmf().unwrap().prob(1);
+    println!("P(sick | +) = {} ({})", p, expected);
+    assert!(estimate.intervals(0.999)[1].contains(expected));
+    assert!((estimate.evidence_probability() - 0.01 * 0.95 - 0.99 * 0.1).abs() < 0.002);
+
+    for positives in 0..=3 {
+        let exact = exact_posterior(
+            start(3),
+            DiagnosticSickRandomVariable,
+            |o| o.positives == positives,
+            0.,
+        )
+        .unwrap();
+        let expected = posterior_sick(prevalence, sensitivity, specificity, positives, 3);
+        assert!((exact.prob(1) - expected).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn two_child_rounds_200_000() {
+    let rounds = 200_000;
+    for evidence in TwoChildEvidence::ALL {
+        let estimate = posterior(
+            TwoChildStartCondition,
+            rounds,
+            TwoChildBoysRandomVariable,
+            |o| evidence.holds(o),
+        );
+        let p = estimate.pmf().unwrap().prob(2);
+        println!(
+            "{:?}: P(two boys) = {} ({})",
+            evidence,
+            p,
+            evidence.two_boys_probability()
+        );
+        assert!(estimate.intervals(0.999)[2].contains(evidence.two_boys_probability()));
+
+        let exact = exact_posterior(
+            TwoChildStartCondition,
+            TwoChildBoysRandomVariable,
+            |o| evidence.holds(o),
+            0.,
+        )
+        .unwrap();
+        assert!((exact.prob(2) - evidence.two_boys_probability()).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn bertrand_box_rounds_100_000() {
+    let rounds = 100_000;
+    let estimate = posterior(
+        BertrandStartCondition::classic(),
+        rounds,
+        BertrandOtherGoldRandomVariable,
+        |o| o.drawn == Coin::Gold,
+    );
+    let expected = other_gold_probability(&BertrandStartCondition::classic().boxes);
+    assert!((expected - 2. / 3.).abs() < 1e-12);
+    assert!((estimate.pmf().unwrap().prob(1) - expected).abs() < 0.01);
+
+    let boxes = vec![
+        [Coin::Gold, Coin::Gold],
+        [Coin::Gold, Coin::Silver],
+        [Coin::Gold, Coin::Silver],
+        [Coin::Silver, Coin::Silver],
+    ];
+    let exact = exact_posterior(
+        BertrandStartCondition {
+            boxes: boxes.clone(),
+        },
+        BertrandOtherGoldRandomVariable,
+        |o| o.drawn == Coin::Gold,
+        0.,
+    )
+    .unwrap();
+    assert!((exact.prob(1) - other_gold_probability(&boxes)).abs() < 1e-12);
+
+    // Evidence of probability zero has no posterior.
+    assert!(exact_posterior(
+        BertrandStartCondition {
+            boxes: vec![[Coin::Silver, Coin::Silver]],
+        },
+        BertrandOtherGoldRandomVariable,
+        |o| o.drawn == Coin::Gold,
+        0.,
+    )
+    .is_none());
+}
+
+#[test]
+fn impossible_evidence() {
+    let estimate = posterior(
+        BertrandStartCondition::classic(),
+        1_000,
+        BertrandOtherGoldRandomVariable,
+        |_| false,
+    );
+    assert_eq!(estimate.accepted, 0);
+    assert!(estimate.pmf().is_none());
+    assert!(estimate.intervals(0.95).is_empty());
+}
@@ -0,0 +1,65 @@
+use cond_prob_sim::continuous::{sample_repeat_continuous, TimeBinRandomVariable, Untimed};
+use cond_prob_sim::models::birth_death::*;
+use cond_prob_sim::pmf::Pmf;
+use cond_prob_sim::prob_mass_func;
+
+#[test]
+fn extinction_time_rounds_100_000() {
+    let rounds = 100_000;
+    let (initial, birth, death) = (2, 1., 1.5);
+    let start = BDStartCondition {
+        initial,
+        birth,
+        death,
+        max_population: usize::MAX,
+        horizon: f64::INFINITY,
+    };
+    let (width, bins) = (0.5, 20);
+    let mass = sample_repeat_continuous(start, rounds, TimeBinRandomVariable::new(width, bins));
+    let simulated = Pmf::from_mass(&mass, rounds);
+    let cdf = |k: usize| extinction_probability_by(initial, birth, death, k as f64 * width);
+    let reference = Pmf::new(0, (0..bins).map(|k| cdf(k + 1) - cdf(k)).collect());
+    assert!(simulated.total_variation(&reference) < 0.01);
+}
+
+#[test]
+fn supercritical_rounds_20_000() {
+    let rounds = 20_000;
+    let (birth, death) = (1.5, 1.);
+    let start = BDStartCondition {
+        initial: 1,
+        birth,
+        death,
+        max_population: 100,
+        horizon: f64::INFINITY,
+    };
+    let mass = sample_repeat_continuous(start, rounds, Untimed(BDExtinctionRandomVariable));
+    let p_extinct = prob_mass_func(&mass, rounds)[1];
+    println!(
+        "P(extinct) = {} ({})",
+        p_extinct,
+        extinction_probability(1, birth, death)
+    );
+    assert!((p_extinct - extinction_probability(1, birth, death)).abs() < 0.01);
+}
+
+#[test]
+fn population_at_horizon_rounds_20_000() {
+    let rounds = 20_000;
+    let (initial, birth, death, t) = (5, 1., 0.5, 1.);
+    let start = BDStartCondition {
+        initial,
+        birth,
+        death,
+        max_population: usize::MAX,
+        horizon: t,
+    };
+    let n = 200;
+    let mass = sample_repeat_continuous(start, rounds, Untimed(BDPopulationRandomVariable { n }));
+    let simulated = Pmf::from_mass(&mass, rounds);
+    let expected = expected_population(initial, birth, death, t);
+    println!("E[N(t)] = {} ({})", simulated.mean(), expected);
+    assert!(simulated.is_normalized(1e-9));
+    assert!((simulated.mean() - expected).abs() < 5. * simulated.mean_standard_error(rounds));
+    assert!((simulated.prob(0) - extinction_probability_by(initial, birth, death, t)).abs() < 0.01);
+}
@@ -0,0 +1,48 @@
+use cond_prob_sim::models::birthday::*;
+
+#[cfg(test)]
+mod tests {
+    use cond_prob_sim::pmf::Pmf;
+    use cond_prob_sim::{prob_mass_func, sample_repeat};
+
+    use super::*;
+
+    #[test]
+    fn rounds_100_000_days_365() {
+        let rounds = 100_000;
+        let days = 365;
+        let people = 23;
+        let mass = sample_repeat(
+            BirthdayStartCondition { days, people },
+            rounds,
+            BirthdayCollisionRandomVariable,
+        );
+        let p = prob_mass_func(&mass, rounds)[1];
+        println!(
+            "P(collision) = {} ({})",
+            p,
+            collision_probability(days, people)
+        );
+        assert!((p - collision_probability(days, people)).abs() < 0.01);
+        assert!(collision_probability(days, people) > 0.5);
+        assert!(collision_probability(days, people - 1) < 0.5);
+    }
+
+    #[test]
+    fn first_collision_rounds_100_000_days_20() {
+        let rounds = 100_000;
+        let days = 20;
+        let mass = sample_repeat(
+            BirthdayStartCondition {
+                days,
+                people: days + 1,
+            },
+            rounds,
+            BirthdayFirstCollisionRandomVariable { n: days + 1 },
+        );
+        let simulated = Pmf::from_mass(&mass, rounds);
+        let reference = first_collision_pmf(days);
+        assert!(reference.is_normalized(1e-12));
+        assert!(simulated.total_variation(&reference) < 0.01);
+    }
+}
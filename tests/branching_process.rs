@@ -80,3 +80,61 @@ fn rounds_100() {
     println!("p_extinct = {}", p_extinct);
     assert!(p_extinct == 1.);
 }
+
+mod galton_watson {
+    use cond_prob_sim::models::branching_process::*;
+    use cond_prob_sim::pmf::Pmf;
+    use cond_prob_sim::{prob_mass_func, sample_repeat};
+
+    #[test]
+    fn supercritical_rounds_10_000() {
+        let rounds = 10_000;
+        // $G(s) = 1/4 + s/4 + s^2/2$ has the fixed points 1/2 and 1.
+        let offspring = Pmf::new(0, vec![0.25, 0.25, 0.5]);
+        let q = extinction_probability(&offspring, 1);
+        println!("q = {}", q);
+        assert!((q - 0.5).abs() < 1e-12);
+        assert!((extinction_probability(&offspring, 2) - 0.25).abs() < 1e-12);
+
+        let horizon = 20;
+        let start = GWStartCondition {
+            offspring: offspring.clone(),
+            initial: 1,
+            horizon,
+            max_population: 200,
+        };
+        let mass = sample_repeat(start, rounds, GWExtinctionRandomVariable);
+        let p_extinct = prob_mass_func(&mass, rounds)[1];
+        println!("p_extinct = {}", p_extinct);
+        assert!((p_extinct - extinction_probability_by(&offspring, 1, horizon)).abs() < 0.02);
+    }
+
+    #[test]
+    fn subcritical_rounds_20_000() {
+        let rounds = 20_000;
+        let n = 1_000;
+        // Mean number of children is 3/4.
+        let offspring = Pmf::new(0, vec![0.5, 0.25, 0.25]);
+        assert!((extinction_probability(&offspring, 3) - 1.).abs() < 1e-12);
+
+        let start = || GWStartCondition {
+            offspring: offspring.clone(),
+            initial: 1,
+            horizon: usize::MAX,
+            max_population: usize::MAX,
+        };
+        let mass = sample_repeat(start(), rounds, GWTotalProgenyRandomVariable { n });
+        let total_progeny = Pmf::from_mass(&mass, rounds);
+        println!("E[total progeny] = {}", total_progeny.mean());
+        // $1 / (1 - \mu)$
+        assert!((total_progeny.mean() - 4.).abs() < 0.2);
+
+        let mass = sample_repeat(start(), rounds, GWExtinctionTimeRandomVariable { n });
+        let extinction_time = Pmf::from_mass(&mass, rounds);
+        for t in 0..5 {
+            let exact = extinction_probability_by(&offspring, 1, t);
+            println!("P(T <= {t}) = {} ({})", extinction_time.cdf(t), exact);
+            assert!((extinction_time.cdf(t) - exact).abs() < 0.02);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use cond_prob_sim::models::cards::*;
+
+#[cfg(test)]
+mod tests {
+    use cond_prob_sim::analytic::{Distribution, Hypergeometric};
+    use cond_prob_sim::pmf::Pmf;
+    use cond_prob_sim::sample_repeat;
+
+    use super::*;
+
+    #[test]
+    fn hearts_in_hand_rounds_100_000() {
+        let rounds = 100_000;
+        let n = 5;
+        let deck = Deck::standard();
+        assert_eq!(deck.len(), 52);
+        assert!(Deck::shoe(0).is_empty());
+
+        let rv = CardCountRandomVariable {
+            matches: |card: &Card| card.suit == Suit::Hearts,
+            n,
+        };
+        let mass = sample_repeat(DrawStartCondition { deck, n }, rounds, rv);
+        let simulated = Pmf::from_mass(&mass, rounds);
+        let reference = Hypergeometric {
+            successes: 13,
+            failures: 39,
+            n,
+        }
+        .pmf(n + 1);
+        assert!(simulated.total_variation(&reference) < 0.01);
+    }
+
+    #[test]
+    fn dealer_rounds_100_000_shoe_8() {
+        let rounds = 100_000;
+        let start = |upcard, hit_soft_17| DealerStartCondition {
+            deck: Deck::shoe(8),
+            upcard,
+            hit_soft_17,
+        };
+
+        for hit_soft_17 in [false, true] {
+            let mass = sample_repeat(start(None, hit_soft_17), rounds, DealerTotalRandomVariable);
+            let simulated = Pmf::from_mass(&mass, rounds);
+            let reference = infinite_shoe_dealer_pmf(None, hit_soft_17);
+            println!("P(bust) = {} ({})", simulated.prob(22), reference.prob(22));
+            assert!(reference.is_normalized(1e-12));
+            assert!(simulated.total_variation(&reference) < 0.01);
+        }
+        let bust = |hit_soft_17| infinite_shoe_dealer_pmf(None, hit_soft_17).prob(22);
+        assert!(bust(true) > bust(false));
+
+        // The dealer busts most often when showing a six.
+        let six = Card {
+            rank: 6,
+            suit: Suit::Spades,
+        };
+        let mass = sample_repeat(start(Some(six), false), rounds, DealerTotalRandomVariable);
+        let simulated = Pmf::from_mass(&mass, rounds);
+        let reference = infinite_shoe_dealer_pmf(Some(6), false);
+        assert!(simulated.total_variation(&reference) < 0.01);
+        for rank in (1..=13).filter(|&rank| rank != 6) {
+            assert!(infinite_shoe_dealer_pmf(Some(rank), false).prob(22) < reference.prob(22));
+        }
+    }
+
+    #[test]
+    fn dealer_stands_when_the_deck_runs_out() {
+        let two = |suit| Card { rank: 2, suit };
+        let start = DealerStartCondition {
+            deck: Deck::from_cards([two(Suit::Clubs), two(Suit::Hearts)]),
+            upcard: None,
+            hit_soft_17: false,
+        };
+        let mass = sample_repeat(start, 10, DealerTotalRandomVariable);
+        assert_eq!(mass[4], 10);
+        assert!(Deck::shoe(0).event_space().is_empty());
+    }
+}
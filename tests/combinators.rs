@@ -0,0 +1,83 @@
+use cond_prob_sim::combinators::*;
+use cond_prob_sim::models::dice::*;
+use cond_prob_sim::pmf::Pmf;
+use cond_prob_sim::{prob_mass_func, sample_repeat, NonnegativeRandomVariable};
+
+mod common;
+
+use common::assert_proportion;
+
+fn two_dice() -> DiceStartCondition {
+    DiceStartCondition {
+        dice: vec![Die::fair(6), Die::fair(6)],
+    }
+}
+
+fn face(i: usize) -> Mapped<DiceOutcome, impl Fn(DiceOutcome) -> usize> {
+    Mapped::new(7, move |o: DiceOutcome| o.faces[i])
+}
+
+#[test]
+fn sum_rounds_100_000() {
+    let rounds = 100_000;
+    let mass = sample_repeat(two_dice(), rounds, Sum(face(0), face(1)));
+    assert_eq!(mass.len(), 13);
+    let simulated = Pmf::from_mass(&mass, rounds);
+    assert!(simulated.total_variation(&Die::fair(6).sum_pmf(2)) < 0.01);
+}
+
+#[test]
+fn max_and_min_rounds_100_000() {
+    let rounds = 100_000;
+    // $P(\max \le k) = (k / 6)^2$ and $P(\min \ge k) = ((7 - k) / 6)^2$.
+    let cdf = |k: usize| (k as f64 / 6.).powi(2);
+    let max = Pmf::new(1, (1..=6).map(|k| cdf(k) - cdf(k - 1)).collect());
+    let min = Pmf::new(1, (1..=6).map(|k| cdf(7 - k) - cdf(6 - k)).collect());
+
+    let mass = sample_repeat(two_dice(), rounds, Max(face(0), face(1)));
+    assert!(Pmf::from_mass(&mass, rounds).total_variation(&max) < 0.01);
+    let mass = sample_repeat(two_dice(), rounds, Min(face(0), face(1)));
+    assert!(Pmf::from_mass(&mass, rounds).total_variation(&min) < 0.01);
+}
+
+#[test]
+fn indicator_rounds_100_000() {
+    let rounds = 100_000;
+    let doubles = Indicator::new(|o: &DiceOutcome| o.faces[0] == o.faces[1]);
+    let mass = sample_repeat(two_dice(), rounds, doubles);
+    let p = prob_mass_func(&mass, rounds)[1];
+    let exact = 1. / 6.;
+    assert_proportion(p, exact, rounds);
+}
+
+#[test]
+fn clamp_rounds_100_000() {
+    let rounds = 100_000;
+    let rv = Clamp::new(DiceSumRandomVariable { n: 12 }, 4..=10);
+    assert_eq!(rv.space_len(), 11);
+    let mass = sample_repeat(two_dice(), rounds, rv);
+    let simulated = Pmf::from_mass(&mass, rounds);
+
+    let sum = Die::fair(6).sum_pmf(2);
+    let mut reference = sum.truncate(4..=10).to_vec();
+    reference[4] += sum.cdf(3);
+    reference[10] += sum.survival(10);
+    assert!(simulated.total_variation(&Pmf::from(reference)) < 0.01);
+}
+
+#[test]
+fn values_outside_the_space_stay_outside() {
+    let outcome = || DiceOutcome { faces: vec![3, 9] };
+    assert_eq!(Sum(face(0), face(1)).map(outcome()), usize::MAX);
+    assert_eq!(Max(face(0), face(1)).map(outcome()), usize::MAX);
+    assert_eq!(Min(face(0), face(1)).map(outcome()), usize::MAX);
+    assert_eq!(Clamp::new(face(1), 1..=4).map(outcome()), usize::MAX);
+    assert_eq!(Clamp::new(face(0), 1..=2).map(outcome()), 2);
+}
+
+#[test]
+#[should_panic(expected = "empty support")]
+fn clamp_to_empty_support() {
+    let (start, end) = (4, 2);
+    Clamp::new(face(0), start..=end);
+}
@@ -0,0 +1,15 @@
+//! Helpers shared by the integration tests.
+
+/// Assert that a proportion simulated in `rounds` rounds is within five standard errors of the
+/// exact probability.
+///
+/// An exact probability of 0 or 1 has no standard error, so the simulation must match it exactly.
+pub fn assert_proportion(simulated: f64, exact: f64, rounds: usize) {
+    let se = (exact * (1. - exact) / rounds as f64).sqrt();
+    assert!(
+        (simulated - exact).abs() <= 5. * se,
+        "{} is more than five standard errors from {}",
+        simulated,
+        exact
+    );
+}
@@ -0,0 +1,35 @@
+use cond_prob_sim::models::coupon_collector::*;
+
+#[cfg(test)]
+mod tests {
+    use cond_prob_sim::pmf::Pmf;
+    use cond_prob_sim::sample_repeat;
+
+    use super::*;
+
+    #[test]
+    fn rounds_100_000_n_6() {
+        let rounds = 100_000;
+        let n = 6;
+        let len = 80;
+        let mass = sample_repeat(CCStartCondition { n }, rounds, CCRandomVariable { n: len });
+        let simulated = Pmf::from_mass(&mass, rounds);
+        let reference = draws_pmf(n, len + 1);
+        println!("E[T] = {} ({})", simulated.mean(), expected_draws(n));
+        assert!(simulated.total_variation(&reference) < 0.01);
+        assert!((reference.mean() - expected_draws(n)).abs() < 1e-3);
+        assert!((reference.variance() - draws_variance(n)).abs() < 0.1);
+        assert!(
+            (simulated.mean() - expected_draws(n)).abs()
+                < 5. * simulated.mean_standard_error(rounds)
+        );
+    }
+
+    #[test]
+    fn no_coupons() {
+        assert_eq!(draws_pmf(0, 3).probs(), [1., 0., 0.]);
+        assert_eq!(expected_draws(0), 0.);
+        let mass = sample_repeat(CCStartCondition { n: 0 }, 10, CCRandomVariable { n: 2 });
+        assert_eq!(mass, [10, 0, 0]);
+    }
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "serde")]
+
+use cond_prob_sim::declarative::{Model, ModelError};
+use cond_prob_sim::exact::exact_prob_mass_func;
+use cond_prob_sim::{prob_mass_func, sample_repeat};
+
+#[test]
+fn monty_hall_toml() {
+    let model = Model::from_toml_str(include_str!("../examples/monty_hall.toml")).unwrap();
+    let rounds = 100_000;
+    let mass = sample_repeat(&model, rounds, model.random_variable());
+    let prob_mass_func = prob_mass_func(&mass, rounds);
+    println!("PMF: {:?}", prob_mass_func);
+    assert!((prob_mass_func[1] - 2. / 3.).abs() < 0.01);
+    let exact = exact_prob_mass_func(&model, model.random_variable(), 0.);
+    println!("Exact PMF: {:?}", exact);
+    assert!((exact[1] - 2. / 3.).abs() < 1e-12);
+}
+
+#[test]
+fn gambler_s_ruin_json() {
+    let model = Model::from_json_str(include_str!("../examples/gambler_s_ruin.json")).unwrap();
+    let exact = exact_prob_mass_func(&model, model.random_variable(), 1e-12);
+    println!("Exact PMF: {:?}", exact);
+    let r: f64 = 0.6 / 0.4;
+    let p_a_wins = (1. - r.powi(2)) / (1. - r.powi(4));
+    assert!((exact[1] - p_a_wins).abs() < 1e-9);
+}
+
+#[test]
+fn undefined_state() {
+    let model = Model::from_toml_str(
+        r#"
+start = "a"
+random_variable = {}
+
+[states.a]
+transitions = [{ to = "b", weight = 1 }]
+"#,
+    );
+    assert_eq!(
+        model.unwrap_err(),
+        ModelError::UnknownState {
+            from: "a".into(),
+            to: "b".into()
+        }
+    );
+}
+
+#[test]
+fn zero_weight() {
+    let model = Model::from_toml_str(
+        r#"
+start = "a"
+random_variable = { end = 0 }
+
+[states.a]
+transitions = [{ to = "b", weight = 0 }]
+
+[states.b]
+outcome = "end"
+"#,
+    );
+    assert_eq!(model.unwrap_err(), ModelError::ZeroWeight("a".into()));
+}
+
+#[test]
+fn unreachable_outcome() {
+    let model = Model::from_toml_str(
+        r#"
+start = "a"
+random_variable = { hit = 1, miss = 0, never = 2 }
+
+[states.a]
+transitions = [{ to = "b", weight = 1 }, { to = "c", weight = 1 }, { to = "d", weight = 0 }]
+
+[states.b]
+outcome = "hit"
+
+[states.c]
+outcome = "miss"
+
+[states.d]
+outcome = "never"
+"#,
+    );
+    assert_eq!(
+        model.unwrap_err(),
+        ModelError::UnreachableOutcome("never".into())
+    );
+}
+
+#[test]
+fn no_outcome() {
+    let model = Model::from_toml_str(
+        r#"
+start = "a"
+random_variable = { end = 0 }
+
+[states.a]
+transitions = [{ to = "end", weight = 1 }, { to = "b", weight = 1 }]
+
+[states.b]
+transitions = [{ to = "b", weight = 1 }]
+
+[states.end]
+outcome = "end"
+"#,
+    );
+    assert_eq!(model.unwrap_err(), ModelError::NoOutcome("b".into()));
+}
@@ -0,0 +1,87 @@
+use cond_prob_sim::models::dice::*;
+
+mod common;
+
+use common::assert_proportion;
+
+#[cfg(test)]
+mod tests {
+    use cond_prob_sim::pmf::Pmf;
+    use cond_prob_sim::{prob_mass_func, sample_repeat};
+
+    use super::*;
+
+    #[test]
+    fn sum_rounds_100_000_three_dice() {
+        let rounds = 100_000;
+        let fair = Die::fair(6);
+        let loaded = Die::loaded(&[1., 1., 1., 1., 1., 5.]);
+        let dice = vec![fair.clone(), fair.clone(), loaded.clone()];
+
+        let mass = sample_repeat(
+            DiceStartCondition { dice: dice.clone() },
+            rounds,
+            DiceSumRandomVariable { n: 18 },
+        );
+        let simulated = Pmf::from_mass(&mass, rounds);
+        let reference = fair.sum_pmf(2).convolve(&loaded.pmf());
+        assert!(reference.is_normalized(1e-12));
+        assert!(simulated.total_variation(&reference) < 0.01);
+
+        let mass = sample_repeat(
+            DiceStartCondition { dice },
+            rounds,
+            DiceMaxRandomVariable { n: 6 },
+        );
+        let simulated = Pmf::from_mass(&mass, rounds);
+        // $P(\max \le k) = (k / 6)^2 \cdot F(k)$ with $F$ the CDF of the loaded die.
+        let cdf = |k: usize| (k as f64 / 6.).powi(2) * loaded.pmf().cdf(k);
+        for k in 1..=6 {
+            assert!((simulated.prob(k) - (cdf(k) - cdf(k - 1))).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn craps_rounds_100_000() {
+        let rounds = 100_000;
+        let die = Die::fair(6);
+        assert!((craps_win_probability(&die) - 244. / 495.).abs() < 1e-12);
+
+        let mass = sample_repeat(
+            CrapsStartCondition { die: die.clone() },
+            rounds,
+            CrapsWinRandomVariable,
+        );
+        let p = prob_mass_func(&mass, rounds)[1];
+        println!("P(win) = {} ({})", p, 244. / 495.);
+        assert!((p - 244. / 495.).abs() < 0.01);
+
+        // Loading the six makes 7 and 11 likelier on the come-out roll.
+        let loaded = Die::loaded(&[1., 1., 1., 1., 1., 2.]);
+        let mass = sample_repeat(
+            CrapsStartCondition {
+                die: loaded.clone(),
+            },
+            rounds,
+            CrapsWinRandomVariable,
+        );
+        let p = prob_mass_func(&mass, rounds)[1];
+        assert!((p - craps_win_probability(&loaded)).abs() < 0.01);
+    }
+
+    #[test]
+    fn craps_d8_rounds_100_000() {
+        let rounds = 100_000;
+        // Totals above 12 are points too.
+        let die = Die::fair(8);
+        let mass = sample_repeat(
+            CrapsStartCondition { die: die.clone() },
+            rounds,
+            CrapsWinRandomVariable,
+        );
+        let p = prob_mass_func(&mass, rounds)[1];
+        let exact = craps_win_probability(&die);
+        println!("P(win) = {} ({})", p, exact);
+        assert_proportion(p, exact, rounds);
+    }
+}
@@ -0,0 +1,90 @@
+use cond_prob_sim::exact::exact_prob_mass_func;
+use cond_prob_sim::models::epidemic::*;
+use cond_prob_sim::pmf::Pmf;
+use cond_prob_sim::{prob_mass_func, sample, sample_repeat, StartCondition};
+
+#[test]
+fn sir_final_size_rounds_100_000() {
+    let rounds = 100_000;
+    let start = || SIRStartCondition {
+        population: 10,
+        infected: 1,
+        beta: 2.,
+        gamma: 1.,
+    };
+    let rv = || SIRFinalSizeRandomVariable { population: 10 };
+    let simulated = Pmf::from_mass(&sample_repeat(start(), rounds, rv()), rounds);
+    let exact = Pmf::from(exact_prob_mass_func(start(), rv(), 1e-12));
+    assert!(exact.is_normalized(1e-9));
+    assert!(simulated.total_variation(&exact) < 0.01);
+}
+
+#[test]
+fn sir_major_outbreak_rounds_10_000() {
+    let rounds = 10_000;
+    let (population, r0) = (500, 2.);
+    let start = SIRStartCondition {
+        population,
+        infected: 1,
+        beta: r0,
+        gamma: 1.,
+    };
+    let mut major = 0;
+    let mut major_size = 0;
+    for _ in 0..rounds {
+        let outcome = sample(start.build());
+        if outcome.final_size > population / 10 {
+            major += 1;
+            major_size += outcome.final_size;
+        }
+    }
+
+    let p_minor = 1. - major as f64 / rounds as f64;
+    // The branching approximation ignores the depletion of susceptibles, which makes minor
+    // outbreaks slightly more likely in a finite population.
+    let approx = minor_outbreak_probability(r0, 1);
+    println!("P(minor) = {} ({})", p_minor, approx);
+    assert!((p_minor - approx).abs() < 0.03);
+
+    let fraction = major_size as f64 / major as f64 / population as f64;
+    println!("final size = {} ({})", fraction, final_size_fraction(r0));
+    assert!((fraction - final_size_fraction(r0)).abs() < 0.01);
+}
+
+#[test]
+fn sis_extinction_rounds_10_000() {
+    let rounds = 10_000;
+    let (population, r0) = (200, 2.);
+    let start = || SISStartCondition {
+        population,
+        infected: 1,
+        beta: r0,
+        gamma: 1.,
+        horizon: 2_000,
+    };
+
+    let mass = sample_repeat(start(), rounds, SISExtinctionRandomVariable);
+    let p_extinct = prob_mass_func(&mass, rounds)[1];
+    let approx = minor_outbreak_probability(r0, 1);
+    println!("P(extinct) = {} ({})", p_extinct, approx);
+    assert!((p_extinct - approx).abs() < 0.03);
+
+    let mass = sample_repeat(start(), rounds, SISEndemicRandomVariable { population });
+    let endemic = Pmf::from(mass.iter().map(|&m| m as f64).collect::<Vec<_>>()).normalize();
+    println!(
+        "endemic level = {} ({})",
+        endemic.mean(),
+        endemic_level(population, r0)
+    );
+    assert!((endemic.mean() - endemic_level(population, r0)).abs() < 0.02 * population as f64);
+}
+
+#[test]
+fn final_size_equation() {
+    for r0 in [0.5, 1., 1.5, 2., 4.] {
+        let z = final_size_fraction(r0);
+        assert!((z - (1. - (-r0 * z).exp())).abs() < 1e-12);
+    }
+    assert_eq!(final_size_fraction(0.9), 0.);
+    assert!((final_size_fraction(2.) - 0.796_812).abs() < 1e-6);
+}
@@ -1,65 +1,6 @@
-use cond_prob_sim::{sample, select, Condition, Event, Outcome};
-
-#[derive(Debug, Clone)]
-pub struct GREvent(usize);
-
-impl Event for GREvent {}
-
-#[derive(Debug)]
-pub enum GROutcome {
-    AWin,
-    BWin,
-}
-
-impl Outcome for GROutcome {}
-
-pub struct GRCondition {
-    /// Amount of money A has.
-    event: GREvent,
-    /// Criteria for A winning.
-    n: usize,
-    /// Probability of A winning.
-    p: f64,
-}
-
-impl GRCondition {
-    pub fn new(i: usize, n: usize, p: f64) -> Self {
-        Self {
-            event: GREvent(i),
-            n,
-            p,
-        }
-    }
-}
-
-impl Condition for GRCondition {
-    type Event = GREvent;
-    type Outcome = GROutcome;
-
-    fn push(&mut self, event: Self::Event) {
-        self.event = event;
-    }
-
-    fn outcome(&self) -> Option<Self::Outcome> {
-        assert!(self.event.0 <= self.n);
-        match self.event.0 {
-            0 => Some(GROutcome::BWin),
-            x if x == self.n => Some(GROutcome::AWin),
-            _ => None,
-        }
-    }
-
-    fn select_event(&self) -> Self::Event {
-        assert!(self.event.0 > 0);
-        assert!(self.event.0 < self.n);
-        let q = 1. - self.p;
-        let space = &[
-            (self.p, GREvent(self.event.0 + 1)),
-            (q, GREvent(self.event.0 - 1)),
-        ];
-        select(space).clone()
-    }
-}
+use cond_prob_sim::analytic::gambler_s_ruin_win;
+use cond_prob_sim::models::gambler_s_ruin::*;
+use cond_prob_sim::sample;
 
 #[test]
 fn i_5_n_100_p_0_5() {
@@ -86,4 +27,100 @@ fn i_5_n_100_p_0_5() {
     println!("p(B wins) = {}", p_b_wins);
     assert!((p_a_wins - 0.05).abs() < 0.01);
     assert!((p_b_wins - 0.95).abs() < 0.01);
+    assert!((p_a_wins - gambler_s_ruin_win(i, n, p)).abs() < 0.01);
+}
+
+mod generalized {
+    use cond_prob_sim::exact::exact_prob_mass_func;
+    use cond_prob_sim::models::gambler_s_ruin::*;
+    use cond_prob_sim::pmf::Pmf;
+    use cond_prob_sim::{sample_repeat, StartCondition};
+
+    #[test]
+    fn ties_rounds_50_000_i_3_n_10() {
+        let rounds = 50_000;
+        let (i, n, p, r) = (3, 10, 0.3, 0.4);
+        let q = 1. - p - r;
+        let start = || GRGameStartCondition::with_ties(i, n, p, r);
+
+        let mass = sample_repeat(start(), rounds, GRGameWinRandomVariable);
+        let p_win = Pmf::from_mass(&mass, rounds).prob(1);
+        println!("p(win) = {} ({})", p_win, win_probability(i, n, p, q));
+        assert!((p_win - win_probability(i, n, p, q)).abs() < 0.01);
+
+        let mass = sample_repeat(start(), rounds, GRGameDurationRandomVariable { n: 10_000 });
+        let duration = Pmf::from_mass(&mass, rounds);
+        let exact = expected_duration(i, n, p, q);
+        println!("E[duration] = {} ({})", duration.mean(), exact);
+        assert!((duration.mean() - exact).abs() < 5. * duration.mean_standard_error(rounds));
+
+        let mass = sample_repeat(start(), rounds, GRGameMaxBankrollRandomVariable { n });
+        let max_bankroll = Pmf::from_mass(&mass, rounds);
+        let exact = max_bankroll_pmf(i, n, p, q);
+        println!("max bankroll: {:?}", max_bankroll.probs());
+        assert!(exact.is_normalized(1e-12));
+        assert!(max_bankroll.total_variation(&exact) < 0.01);
+    }
+
+    #[test]
+    #[should_panic(expected = "step probabilities")]
+    fn negative_step_probability() {
+        GRGameStartCondition::with_ties(2, 4, 0.7, 0.5).build();
+    }
+
+    #[test]
+    #[should_panic(expected = "step probabilities")]
+    fn steps_not_summing_to_one() {
+        GRGameCondition::new(
+            2,
+            Barrier::Absorbing(0),
+            Barrier::Absorbing(4),
+            &[(0.5, 1), (0.3, -1)],
+            usize::MAX,
+        );
+    }
+
+    #[test]
+    fn boundary_max_bankroll() {
+        assert_eq!(max_bankroll_pmf(0, 5, 0.4, 0.4).probs(), [1.]);
+        assert_eq!(max_bankroll_pmf(0, 0, 0.4, 0.4).probs(), [1.]);
+        let won = max_bankroll_pmf(3, 3, 0.4, 0.5);
+        assert_eq!((won.offset(), won.probs()), (3, &[1.][..]));
+    }
+
+    #[test]
+    fn reflecting_lower_barrier_rounds_20_000() {
+        let rounds = 20_000;
+        let (i, n) = (2, 6);
+        let start = GRGameStartCondition {
+            bankroll: i,
+            lower: Barrier::Reflecting(0),
+            upper: Barrier::Absorbing(n),
+            steps: vec![(0.5, 1), (0.5, -1)],
+            horizon: usize::MAX,
+        };
+        let mass = sample_repeat(start, rounds, GRGameDurationRandomVariable { n: 10_000 });
+        let duration = Pmf::from_mass(&mass, rounds);
+        // Holding the bankroll at 0 gives $E_i = n (n + 1) - i (i + 1)$.
+        let exact = (n * (n + 1) - i * (i + 1)) as f64;
+        println!("E[duration] = {} ({})", duration.mean(), exact);
+        assert!((duration.mean() - exact).abs() < 5. * duration.mean_standard_error(rounds));
+    }
+
+    #[test]
+    fn variable_stakes_rounds_50_000() {
+        let rounds = 50_000;
+        let start = || GRGameStartCondition {
+            bankroll: 4,
+            lower: Barrier::Absorbing(0),
+            upper: Barrier::Absorbing(10),
+            steps: vec![(0.3, 3), (0.2, 0), (0.5, -2)],
+            horizon: 200,
+        };
+        let exact = exact_prob_mass_func(start(), GRGameWinRandomVariable, 1e-12);
+        let mass = sample_repeat(start(), rounds, GRGameWinRandomVariable);
+        let simulated = Pmf::from_mass(&mass, rounds);
+        println!("PMF: {:?} ({:?})", simulated.probs(), exact);
+        assert!(simulated.total_variation(&Pmf::from(exact)) < 0.01);
+    }
 }
@@ -0,0 +1,67 @@
+use cond_prob_sim::exact::exact_prob_mass_func;
+use cond_prob_sim::models::genetic_drift::*;
+use cond_prob_sim::pmf::Pmf;
+use cond_prob_sim::{prob_mass_func, sample_repeat};
+
+mod common;
+
+use common::assert_proportion;
+
+#[test]
+fn moran_fixation_rounds_20_000() {
+    let rounds = 20_000;
+    let (population, count, selection) = (10, 3, 0.2);
+    let start = || MoranStartCondition {
+        population,
+        count,
+        selection,
+    };
+    let exact = moran_fixation_probability(population, count, selection);
+
+    let pmf = exact_prob_mass_func(start(), FixationRandomVariable, 1e-12);
+    assert!((pmf[1] - exact).abs() < 1e-9);
+
+    let mass = sample_repeat(start(), rounds, FixationRandomVariable);
+    let p_fixed = prob_mass_func(&mass, rounds)[1];
+    println!("P(fixed) = {} ({})", p_fixed, exact);
+    assert_proportion(p_fixed, exact, rounds);
+}
+
+#[test]
+fn wright_fisher_neutral_rounds_10_000() {
+    let rounds = 10_000;
+    let (population, count) = (50, 10);
+    let start = || WFStartCondition {
+        population,
+        count,
+        selection: 0.,
+    };
+
+    let mass = sample_repeat(start(), rounds, FixationRandomVariable);
+    let p_fixed = prob_mass_func(&mass, rounds)[1];
+    let exact = kimura_fixation_probability(population, count, 0.);
+    assert_proportion(p_fixed, exact, rounds);
+
+    let n = 2_000;
+    let mass = sample_repeat(start(), rounds, AbsorptionTimeRandomVariable { n });
+    let time = Pmf::from_mass(&mass, rounds);
+    let approx = neutral_absorption_time(population, count);
+    println!("E[T] = {} ({})", time.mean(), approx);
+    assert!((time.mean() - approx).abs() < 0.1 * approx);
+}
+
+#[test]
+fn wright_fisher_selection_rounds_5_000() {
+    let rounds = 5_000;
+    let (population, count, selection) = (100, 5, 0.05);
+    let start = WFStartCondition {
+        population,
+        count,
+        selection,
+    };
+    let mass = sample_repeat(start, rounds, FixationRandomVariable);
+    let p_fixed = prob_mass_func(&mass, rounds)[1];
+    let approx = kimura_fixation_probability(population, count, selection);
+    println!("P(fixed) = {} ({})", p_fixed, approx);
+    assert!((p_fixed - approx).abs() < 0.03);
+}
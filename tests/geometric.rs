@@ -1,94 +1,4 @@
-use cond_prob_sim::{select, Condition, Event, NonnegativeRandomVariable, Outcome, StartCondition};
-
-#[derive(Debug, Clone)]
-pub enum GeomEvent {
-    Success,
-    Failure,
-}
-
-impl Event for GeomEvent {}
-
-pub struct GeomOutcome {
-    pub failures: usize,
-}
-
-impl Outcome for GeomOutcome {}
-
-pub struct GeomCondition {
-    failures: usize,
-    succeeded: bool,
-    /// Probability of success.
-    p: f64,
-}
-
-impl GeomCondition {
-    pub fn new(p: f64) -> Self {
-        Self {
-            failures: 0,
-            succeeded: false,
-            p,
-        }
-    }
-}
-
-impl Condition for GeomCondition {
-    type Event = GeomEvent;
-    type Outcome = GeomOutcome;
-
-    fn push(&mut self, event: Self::Event) {
-        assert!(!self.succeeded);
-        match &event {
-            GeomEvent::Success => self.succeeded = true,
-            GeomEvent::Failure => self.failures += 1,
-        }
-    }
-
-    fn select_event(&self) -> Self::Event {
-        let q = 1. - self.p;
-        let space = &[(self.p, GeomEvent::Success), (q, GeomEvent::Failure)];
-        select(space).clone()
-    }
-
-    fn outcome(&self) -> Option<Self::Outcome> {
-        if self.succeeded {
-            Some(GeomOutcome {
-                failures: self.failures,
-            })
-        } else {
-            None
-        }
-    }
-}
-
-pub struct GeomStartCondition {
-    pub p: f64,
-}
-
-impl StartCondition for GeomStartCondition {
-    type Event = GeomEvent;
-    type Outcome = GeomOutcome;
-    type Condition = GeomCondition;
-
-    fn build(&self) -> Self::Condition {
-        GeomCondition::new(self.p)
-    }
-}
-
-pub struct GeomRandomVariable {
-    pub n: usize,
-}
-
-impl NonnegativeRandomVariable for GeomRandomVariable {
-    type Outcome = GeomOutcome;
-
-    fn map(&self, outcome: Self::Outcome) -> usize {
-        outcome.failures
-    }
-
-    fn space_len(&self) -> usize {
-        self.n + 1
-    }
-}
+use cond_prob_sim::models::geometric::*;
 
 #[cfg(test)]
 mod tests {
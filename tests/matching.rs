@@ -0,0 +1,57 @@
+use cond_prob_sim::models::matching::*;
+
+#[cfg(test)]
+mod tests {
+    use cond_prob_sim::exact::exact_prob_mass_func;
+    use cond_prob_sim::pmf::Pmf;
+    use cond_prob_sim::{sample_repeat, Condition};
+
+    use super::*;
+
+    #[test]
+    fn same_state_by_different_paths() {
+        let fill = |envelopes: [usize; 3]| {
+            let mut cond = MatchingCondition::new(5);
+            for e in envelopes {
+                cond.push(MatchingEvent(e));
+            }
+            cond
+        };
+        // Both fill envelopes 0, 1 and 2 with one match.
+        assert_eq!(fill([0, 2, 1]), fill([1, 0, 2]));
+    }
+
+    #[test]
+    fn rounds_100_000_n_8() {
+        let rounds = 100_000;
+        let n = 8;
+        let mass = sample_repeat(
+            MatchingStartCondition { n },
+            rounds,
+            MatchingRandomVariable { n },
+        );
+        let simulated = Pmf::from_mass(&mass, rounds);
+        let reference = matches_pmf(n);
+        println!("PMF: {:?}", simulated.probs());
+        assert!(reference.is_normalized(1e-12));
+        assert!((reference.prob(0) - derangement_probability(n)).abs() < 1e-12);
+        assert!((derangement_probability(n) - (-1_f64).exp()).abs() < 1e-4);
+        assert!((reference.mean() - 1.).abs() < 1e-12);
+        assert!(simulated.total_variation(&reference) < 0.01);
+    }
+
+    #[test]
+    fn exact_n_4() {
+        let n = 4;
+        // $!4 = 9$ of the $4! = 24$ permutations are derangements.
+        let exact = exact_prob_mass_func(
+            MatchingStartCondition { n },
+            MatchingRandomVariable { n },
+            0.,
+        );
+        let reference = [9., 8., 6., 0., 1.].map(|k| k / 24.);
+        for (p, q) in exact.iter().zip(reference) {
+            assert!((p - q).abs() < 1e-12);
+        }
+    }
+}
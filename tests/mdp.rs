@@ -0,0 +1,79 @@
+use cond_prob_sim::analytic::gambler_s_ruin_win;
+use cond_prob_sim::mdp::Mdp;
+use cond_prob_sim::models::gambler_s_ruin::*;
+use cond_prob_sim::models::secretary::*;
+use cond_prob_sim::policy::ControlledStartCondition;
+use cond_prob_sim::{prob_mass_func, sample_repeat, StartCondition};
+
+#[test]
+fn secretary_n_10() {
+    let rounds = 100_000;
+    let n = 10;
+    let start = SecretaryStartCondition {
+        n,
+        policy: Cutoff { r: 1 },
+    };
+    let mdp = Mdp::explore(start.build(), |o: SecretaryOutcome| o.best as usize as f64);
+    let cutoff = optimal_cutoff(n);
+    let expected = cutoff_success_probability(n, cutoff.r);
+    println!("r = {}, P(best) = {}", cutoff.r, expected);
+    assert_eq!(cutoff.r, 4);
+
+    let values = mdp.value_iteration(1e-12);
+    let policies = mdp.policy_iteration(1e-12);
+    assert!((values.value() - expected).abs() < 1e-9);
+    assert!((policies.value() - expected).abs() < 1e-9);
+    assert!((mdp.evaluate(&cutoff, 1e-12) - expected).abs() < 1e-9);
+    assert!(mdp.evaluate(&Cutoff { r: 2 }, 1e-12) < expected);
+
+    let optimal = ControlledStartCondition {
+        start,
+        policy: &values,
+    };
+    let mass = sample_repeat(optimal, rounds, SecretaryRandomVariable);
+    let p = prob_mass_func(&mass, rounds)[1];
+    assert!((p - expected).abs() < 0.01);
+}
+
+#[test]
+fn betting_bankroll_3_target_10() {
+    let rounds = 100_000;
+    let (bankroll, target) = (3, 10);
+    let start = |p| GRBetStartCondition {
+        bankroll,
+        target,
+        p,
+        policy: BettingPolicy::Timid,
+    };
+    let win = |o: GRBetOutcome| (o == GRBetOutcome::Win) as usize as f64;
+
+    // Bold play is optimal in a subfair game.
+    let mdp = Mdp::explore(start(0.4).build(), win);
+    let solution = mdp.value_iteration(1e-12);
+    let bold = mdp.evaluate(&BettingPolicy::Bold, 1e-12);
+    let timid = gambler_s_ruin_win(bankroll, target, 0.4);
+    println!(
+        "optimal {}, bold {}, timid {}",
+        solution.value(),
+        bold,
+        timid
+    );
+    assert!((solution.value() - bold).abs() < 1e-9);
+    assert!((mdp.policy_iteration(1e-12).value() - bold).abs() < 1e-9);
+    assert!((mdp.evaluate(&BettingPolicy::Timid, 1e-12) - timid).abs() < 1e-9);
+    assert!(bold > timid);
+
+    let optimal = ControlledStartCondition {
+        start: start(0.4),
+        policy: &solution,
+    };
+    let mass = sample_repeat(optimal, rounds, GRBetRandomVariable);
+    let p = prob_mass_func(&mass, rounds)[1];
+    assert!((p - solution.value()).abs() < 0.01);
+
+    // Timid play is optimal in a superfair game.
+    let mdp = Mdp::explore(start(0.6).build(), win);
+    let timid = gambler_s_ruin_win(bankroll, target, 0.6);
+    assert!((mdp.value_iteration(1e-12).value() - timid).abs() < 1e-9);
+    assert!((mdp.policy_iteration(1e-12).value() - timid).abs() < 1e-9);
+}
@@ -0,0 +1,72 @@
+use cond_prob_sim::models::geometric::*;
+use cond_prob_sim::pmf::Pmf;
+use cond_prob_sim::sample_repeat;
+
+/// Geometric PMF on the failures before the first success, truncated at `n`.
+fn geometric(p: f64, n: usize) -> Pmf {
+    Pmf::new(0, (0..=n).map(|k| (1. - p).powi(k as i32) * p).collect())
+}
+
+#[test]
+fn exact_geometric() {
+    let p = 0.5;
+    let q = 1. - p;
+    let pmf = geometric(p, 200);
+    assert!((pmf.mean() - q / p).abs() < 1e-12);
+    assert!((pmf.variance() - q / p.powi(2)).abs() < 1e-12);
+    assert!((pmf.skewness() - (2. - p) / q.sqrt()).abs() < 1e-9);
+    assert!((pmf.excess_kurtosis() - (6. + p * p / q)).abs() < 1e-9);
+    // $E[X (X - 1)] = 2 q^2 / p^2$
+    assert!((pmf.factorial_moment(2) - 2. * q * q / (p * p)).abs() < 1e-9);
+    assert!((pmf.raw_moment(2) - (q / p.powi(2) + (q / p).powi(2))).abs() < 1e-9);
+    let entropy = (-q * q.ln() - p * p.ln()) / p;
+    assert!((pmf.entropy() - entropy).abs() < 1e-9);
+    assert!((pmf.pgf(0.5) - p / (1. - q * 0.5)).abs() < 1e-12);
+    assert!((pmf.mgf(0.1) - p / (1. - q * 0.1_f64.exp())).abs() < 1e-12);
+}
+
+#[test]
+fn rounds_100_000_p_0_5() {
+    let rounds = 100_000;
+    let n = 40;
+    let p = 0.5;
+    let mass = sample_repeat(GeomStartCondition { p }, rounds, GeomRandomVariable { n });
+    let simulated = Pmf::from_mass(&mass, rounds);
+    let exact = geometric(p, 200);
+    let checks = [
+        (
+            "mean",
+            simulated.mean(),
+            exact.mean(),
+            simulated.mean_standard_error(rounds),
+        ),
+        (
+            "variance",
+            simulated.variance(),
+            exact.variance(),
+            simulated.variance_standard_error(rounds),
+        ),
+        (
+            "E[X(X-1)]",
+            simulated.factorial_moment(2),
+            exact.factorial_moment(2),
+            simulated.factorial_moment_standard_error(2, rounds),
+        ),
+        (
+            "entropy",
+            simulated.entropy(),
+            exact.entropy(),
+            simulated.entropy_standard_error(rounds),
+        ),
+        (
+            "G(0.5)",
+            simulated.pgf(0.5),
+            exact.pgf(0.5),
+            simulated.pgf_standard_error(0.5, rounds),
+        ),
+    ];
+    for (name, estimate, truth, standard_error) in checks {
+        println!("{name}: {estimate} ± {standard_error} (exact {truth})");
+        assert!((estimate - truth).abs() < 5. * standard_error);
+    }
+}
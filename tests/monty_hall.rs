@@ -1,79 +1,5 @@
-use cond_prob_sim::{sample, select, Condition, Event, Outcome};
-
-#[derive(Debug, Clone)]
-pub enum MHEvent {
-    Door1,
-    Door2,
-    Door3,
-}
-
-impl Event for MHEvent {}
-
-#[derive(Debug)]
-pub enum MHOutcome {
-    Win,
-    Lose,
-}
-
-impl Outcome for MHOutcome {}
-
-pub struct MHCondition {
-    /// Tuple format:
-    /// > (Your choice, Car's location, Host's choice, Whether you switch)
-    ///
-    /// You always switch in the end.
-    events: Vec<MHEvent>,
-}
-
-impl MHCondition {
-    pub fn new() -> Self {
-        Self {
-            events: Vec::with_capacity(3),
-        }
-    }
-}
-
-impl Default for MHCondition {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl Condition for MHCondition {
-    type Event = MHEvent;
-    type Outcome = MHOutcome;
-
-    fn push(&mut self, event: Self::Event) {
-        self.events.push(event);
-    }
-
-    fn outcome(&self) -> Option<Self::Outcome> {
-        match self.events.as_slice() {
-            [MHEvent::Door1, MHEvent::Door1, MHEvent::Door2] => Some(MHOutcome::Lose),
-            [MHEvent::Door1, MHEvent::Door1, MHEvent::Door3] => Some(MHOutcome::Lose),
-            [MHEvent::Door1, MHEvent::Door2, MHEvent::Door3] => Some(MHOutcome::Win),
-            [MHEvent::Door1, MHEvent::Door3, MHEvent::Door2] => Some(MHOutcome::Win),
-            _ => None,
-        }
-    }
-
-    fn select_event(&self) -> Self::Event {
-        let space: &[(f64, Self::Event)] = match self.events.as_slice() {
-            [MHEvent::Door1] => &[
-                (1.0 / 3.0, MHEvent::Door1),
-                (1.0 / 3.0, MHEvent::Door2),
-                (1.0 / 3.0, MHEvent::Door3),
-            ],
-            [MHEvent::Door1, MHEvent::Door1] => {
-                &[(1.0 / 2.0, MHEvent::Door2), (1.0 / 2.0, MHEvent::Door3)]
-            }
-            [MHEvent::Door1, MHEvent::Door2] => &[(1.0, MHEvent::Door3)],
-            [MHEvent::Door1, MHEvent::Door3] => &[(1.0, MHEvent::Door2)],
-            _ => unreachable!(),
-        };
-        select(space).clone()
-    }
-}
+use cond_prob_sim::models::monty_hall::*;
+use cond_prob_sim::{sample, Condition};
 
 #[test]
 fn rounds_1_000_000() {
@@ -96,3 +22,27 @@ fn rounds_1_000_000() {
     assert!((p_win - 0.66).abs() < 0.01);
     assert!((p_lose - 0.33).abs() < 0.01);
 }
+
+mod generalized {
+    use cond_prob_sim::models::monty_hall::*;
+
+    #[test]
+    fn rounds_50_000() {
+        let rounds = 50_000;
+        let games = [
+            (3, 1, HostStrategy::Knows),
+            (3, 1, HostStrategy::Ignorant),
+            (3, 1, HostStrategy::Biased { p: 0.9 }),
+            (10, 8, HostStrategy::Knows),
+            (5, 2, HostStrategy::Ignorant),
+            (6, 3, HostStrategy::Biased { p: 0.5 }),
+        ];
+        for (doors, opened, host) in games {
+            for (policy, p_win) in win_probabilities(doors, opened, host, rounds) {
+                let exact = exact_win_probability(doors, opened, host, policy);
+                println!("{doors} doors, {opened} opened, {host:?}, {policy:?}: {p_win} ({exact})");
+                assert!((p_win - exact).abs() < 0.01);
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use cond_prob_sim::analytic::{
+    multinomial_prob, multivariate_hypergeometric_prob, Binomial, Distribution, Hypergeometric,
+};
+use cond_prob_sim::exact::exact_prob_mass_func;
+use cond_prob_sim::models::multinomial::*;
+use cond_prob_sim::models::multivariate_hypergeometric::*;
+use cond_prob_sim::pmf::Pmf;
+use cond_prob_sim::{sample, sample_repeat, StartCondition};
+
+#[test]
+fn multinomial_rounds_100_000_n_10() {
+    let rounds = 100_000;
+    let n = 10;
+    let p = vec![0.2, 0.3, 0.5];
+    let start = || MultinomialStartCondition { n, p: p.clone() };
+
+    let mass = sample_repeat(
+        start(),
+        rounds,
+        MultiCategoryRandomVariable { category: 1, n },
+    );
+    let simulated = Pmf::from_mass(&mass, rounds);
+    let reference = Binomial { n, p: 0.3 }.pmf(n + 1);
+    assert!(simulated.total_variation(&reference) < 0.01);
+
+    // Categories 0 and 2 together.
+    let rv = MultiLinearRandomVariable {
+        coefficients: vec![1, 0, 1],
+        n,
+    };
+    let mass = sample_repeat(start(), rounds, rv);
+    let simulated = Pmf::from_mass(&mass, rounds);
+    let reference = Binomial { n, p: 0.7 }.pmf(n + 1);
+    assert!(simulated.total_variation(&reference) < 0.01);
+
+    let counts = [2, 3, 5];
+    let hits = (0..rounds)
+        .filter(|_| sample(start().build()).counts == counts)
+        .count();
+    let p_counts = hits as f64 / rounds as f64;
+    println!(
+        "P(X = {:?}) = {} ({})",
+        counts,
+        p_counts,
+        multinomial_prob(&counts, &p)
+    );
+    assert!((p_counts - multinomial_prob(&counts, &p)).abs() < 0.005);
+}
+
+#[test]
+fn multinomial_exact() {
+    let n = 5;
+    let p = vec![0.1, 0.6, 0.3];
+    let start = MultinomialStartCondition { n, p: p.clone() };
+    // Encode the counts of categories 0 and 1 as $6 X_0 + X_1$.
+    let rv = MultiLinearRandomVariable {
+        coefficients: vec![6, 1, 0],
+        n: 6 * n,
+    };
+    let exact = exact_prob_mass_func(start, rv, 0.);
+    for x0 in 0..=n {
+        for x1 in 0..=n - x0 {
+            let x = [x0, x1, n - x0 - x1];
+            assert!((exact[6 * x0 + x1] - multinomial_prob(&x, &p)).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn multivariate_hypergeometric_rounds_100_000_n_6() {
+    let rounds = 100_000;
+    let n = 6;
+    let population = vec![4, 5, 6];
+    let start = || MultiHGeomStartCondition {
+        remaining: population.clone(),
+        n,
+    };
+
+    let mass = sample_repeat(
+        start(),
+        rounds,
+        MultiCategoryRandomVariable { category: 0, n },
+    );
+    let simulated = Pmf::from_mass(&mass, rounds);
+    let reference = Hypergeometric {
+        successes: 4,
+        failures: 11,
+        n,
+    }
+    .pmf(n + 1);
+    assert!(simulated.total_variation(&reference) < 0.01);
+
+    // Weighted score $2 X_0 + X_1$.
+    let rv = || MultiLinearRandomVariable {
+        coefficients: vec![2, 1, 0],
+        n: 2 * n,
+    };
+    let exact = Pmf::from(exact_prob_mass_func(start(), rv(), 0.));
+    let mut reference = vec![0.; 2 * n + 1];
+    for x0 in 0..=4 {
+        for x1 in 0..=(n - x0).min(5) {
+            let x = [x0, x1, n - x0 - x1];
+            reference[2 * x0 + x1] += multivariate_hypergeometric_prob(&x, &population);
+        }
+    }
+    assert!(exact.total_variation(&Pmf::from(reference)) < 1e-12);
+    let mass = sample_repeat(start(), rounds, rv());
+    assert!(Pmf::from_mass(&mass, rounds).total_variation(&exact) < 0.01);
+}
@@ -0,0 +1,66 @@
+use cond_prob_sim::models::binomial::*;
+use cond_prob_sim::pmf::Pmf;
+use cond_prob_sim::sample_repeat;
+
+fn bernoulli(p: f64) -> Pmf {
+    Pmf::new(0, vec![1. - p, p])
+}
+
+#[test]
+fn convolution_of_bernoullis_is_binomial() {
+    let n = 10;
+    let p = 0.2;
+    let binomial = (0..n).fold(Pmf::point(0), |acc, _| acc.convolve(&bernoulli(p)));
+    assert!(binomial.is_normalized(1e-12));
+    assert_eq!(binomial.support(), 0..=n);
+    assert!((binomial.mean() - n as f64 * p).abs() < 1e-12);
+    assert!((binomial.variance() - n as f64 * p * (1. - p)).abs() < 1e-12);
+    assert_eq!(binomial.mode(), 2);
+    assert_eq!(binomial.quantile(0.5), Some(2));
+    assert!((binomial.cdf(1) - (0.8_f64.powi(10) + 2. * 0.8_f64.powi(9))).abs() < 1e-12);
+    assert!((binomial.cdf(4) + binomial.survival(4) - 1.).abs() < 1e-12);
+
+    let rounds = 100_000;
+    let mass = sample_repeat(BinStartCondition { n, p }, rounds, BinRandomVariable { n });
+    let simulated = Pmf::from_mass(&mass, rounds);
+    let tv = simulated.total_variation(&binomial);
+    println!("TV: {}", tv);
+    assert!(tv < 0.01);
+    assert!(simulated.hellinger(&binomial) < 0.01);
+}
+
+#[test]
+fn transformations() {
+    let die = Pmf::new(1, vec![1. / 6.; 6]);
+    assert_eq!(die.scale(2).support(), 2..=12);
+    assert!((die.scale(2).prob(4) - 1. / 6.).abs() < 1e-12);
+    assert_eq!(die.scale(2).prob(5), 0.);
+    assert_eq!(die.shift(3).support(), 4..=9);
+
+    let even = die.condition(|x| x % 2 == 0).unwrap();
+    assert!((even.prob(2) - 1. / 3.).abs() < 1e-12);
+    assert_eq!(even.prob(3), 0.);
+    assert!(die.condition(|x| x > 6).is_none());
+
+    let low = die.truncate(0..=3);
+    assert_eq!(low.support(), 1..=3);
+    assert!((low.total() - 0.5).abs() < 1e-12);
+    assert!(low.normalize().is_normalized(1e-12));
+
+    let mixture = Pmf::mixture(&[(0.5, &Pmf::point(0)), (0.5, &die)]);
+    assert_eq!(mixture.support(), 0..=6);
+    assert!((mixture.prob(0) - 0.5).abs() < 1e-12);
+    assert!((mixture.mean() - 1.75).abs() < 1e-12);
+}
+
+#[test]
+fn distances() {
+    let p = bernoulli(0.5);
+    let q = bernoulli(0.25);
+    assert!((p.total_variation(&q) - 0.25).abs() < 1e-12);
+    let kl = 0.5 * (0.5_f64 / 0.75).ln() + 0.5 * (0.5_f64 / 0.25).ln();
+    assert!((p.kl_divergence(&q) - kl).abs() < 1e-12);
+    assert_eq!(p.kl_divergence(&Pmf::point(0)), f64::INFINITY);
+    assert_eq!(p.hellinger(&p), 0.);
+    assert!((Pmf::point(0).hellinger(&Pmf::point(1)) - 1.).abs() < 1e-12);
+}
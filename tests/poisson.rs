@@ -0,0 +1,42 @@
+use cond_prob_sim::analytic::{Distribution, Poisson};
+use cond_prob_sim::continuous::{sample_repeat_continuous, Untimed};
+use cond_prob_sim::models::poisson::*;
+use cond_prob_sim::pmf::Pmf;
+
+#[test]
+fn process_rounds_50_000() {
+    let rounds = 50_000;
+    let (rate, horizon) = (2., 3.);
+    let start = || PoissonStartCondition { rate, horizon };
+
+    let n = 30;
+    let mass = sample_repeat_continuous(start(), rounds, Untimed(PoissonCountRandomVariable { n }));
+    let simulated = Pmf::from_mass(&mass, rounds);
+    let reference = Poisson {
+        lambda: rate * horizon,
+    }
+    .pmf(n + 1);
+    assert!(simulated.total_variation(&reference) < 0.01);
+
+    // The first arrival time is exponential.
+    let (width, bins) = (0.1, 15);
+    let cdf = |k: usize| 1. - (-rate * k as f64 * width).exp();
+    let reference = Pmf::new(0, (0..bins).map(|k| cdf(k + 1) - cdf(k)).collect());
+    let rv = PoissonInterArrivalRandomVariable { k: 0, width, bins };
+    let mass = sample_repeat_continuous(start(), rounds, Untimed(rv));
+    let simulated = Pmf::from_mass(&mass, rounds);
+    assert!(simulated.total_variation(&reference) < 0.01);
+}
+
+#[test]
+fn poisson_limit_lambda_3() {
+    let rounds = 20_000;
+    let lambda = 3.;
+    let rows = poisson_limit(lambda, &[10, 30, 100], rounds);
+    for row in &rows {
+        println!("{:?}", row);
+        assert!(row.exact <= row.bound);
+        assert!((row.simulated - row.exact).abs() < 0.02);
+    }
+    assert!(rows.windows(2).all(|w| w[1].exact < w[0].exact));
+}
@@ -0,0 +1,115 @@
+use rand::Rng;
+
+use cond_prob_sim::models::monty_hall::*;
+use cond_prob_sim::policy::{compare_policies, ControlledStartCondition};
+use cond_prob_sim::{prob_mass_func, rng, sample_repeat};
+
+#[test]
+fn monty_hall_policies_rounds_20_000() {
+    let rounds = 20_000;
+    let (doors, opened, host) = (4, 2, HostStrategy::Knows);
+    // The policy of the start condition is overridden at the choice point.
+    let start = MHGameStartCondition {
+        doors,
+        opened,
+        host,
+        policy: PlayerPolicy::Stay,
+    };
+
+    let comparison = compare_policies(
+        &start,
+        &PlayerPolicy::ALL,
+        rounds,
+        &MHGameRandomVariable,
+        0.99,
+    );
+    println!("{:?}", comparison);
+    assert_eq!(PlayerPolicy::ALL[comparison.best], PlayerPolicy::Switch);
+    assert!(comparison.is_significant());
+    for (i, policy) in PlayerPolicy::ALL.into_iter().enumerate() {
+        let expected = exact_win_probability(doors, opened, host, policy);
+        assert!((comparison.means[i] - expected).abs() < 0.02);
+    }
+    let advantage = exact_win_probability(doors, opened, host, PlayerPolicy::Switch)
+        - exact_win_probability(doors, opened, host, PlayerPolicy::Stay);
+    assert!(comparison.differences[0].contains(advantage));
+
+    // Common random numbers make the comparison reproducible.
+    rng::seed(7);
+    let first = compare_policies(&start, &PlayerPolicy::ALL, 100, &MHGameRandomVariable, 0.95);
+    rng::seed(7);
+    let second = compare_policies(&start, &PlayerPolicy::ALL, 100, &MHGameRandomVariable, 0.95);
+    assert_eq!(first, second);
+}
+
+#[test]
+fn controlled_matches_builtin_policy() {
+    let rounds = 100_000;
+    let game = |policy| MHGameStartCondition {
+        doors: 3,
+        opened: 1,
+        host: HostStrategy::Knows,
+        policy,
+    };
+    let controlled = ControlledStartCondition {
+        start: game(PlayerPolicy::Stay),
+        policy: PlayerPolicy::Switch,
+    };
+    let mass = sample_repeat(controlled, rounds, MHGameRandomVariable);
+    let p = prob_mass_func(&mass, rounds)[1];
+    assert!((p - 2. / 3.).abs() < 0.01);
+}
+
+#[test]
+fn void_games_are_skipped() {
+    let rounds = 20_000;
+    let (doors, opened, host) = (3, 1, HostStrategy::Ignorant);
+    let start = MHGameStartCondition {
+        doors,
+        opened,
+        host,
+        policy: PlayerPolicy::Stay,
+    };
+
+    let comparison = compare_policies(
+        &start,
+        &PlayerPolicy::ALL,
+        rounds,
+        &MHGameRandomVariable,
+        0.99,
+    );
+    println!("{:?}", comparison);
+    assert_eq!(comparison.rounds + comparison.skipped, rounds);
+    // The ignorant host reveals the car in a third of the games.
+    let void = comparison.skipped as f64 / rounds as f64;
+    assert!((void - 1. / 3.).abs() < 0.02);
+    for (i, policy) in PlayerPolicy::ALL.into_iter().enumerate() {
+        let expected = exact_win_probability(doors, opened, host, policy);
+        assert!((comparison.means[i] - expected).abs() < 0.02);
+    }
+}
+
+#[test]
+fn comparison_only_draws_the_seeds() {
+    let rounds = 100;
+    let start = MHGameStartCondition {
+        doors: 3,
+        opened: 1,
+        host: HostStrategy::Knows,
+        policy: PlayerPolicy::Stay,
+    };
+
+    rng::seed(11);
+    // The comparison draws one seed per round and nothing else.
+    let expected = rng::with_rng(|rng| (0..=rounds).map(|_| rng.gen::<u64>()).last().unwrap());
+
+    rng::seed(11);
+    compare_policies(
+        &start,
+        &PlayerPolicy::ALL,
+        rounds,
+        &MHGameRandomVariable,
+        0.95,
+    );
+    assert_eq!(rng::with_rng(|rng| rng.gen::<u64>()), expected);
+}
@@ -0,0 +1,119 @@
+use cond_prob_sim::analytic::{Distribution, Geometric};
+use cond_prob_sim::continuous::{sample_repeat_continuous, Untimed};
+use cond_prob_sim::models::queue::*;
+use cond_prob_sim::pmf::Pmf;
+use cond_prob_sim::prob_mass_func;
+
+mod common;
+
+use common::assert_proportion;
+
+#[test]
+fn closed_forms() {
+    let (arrival, service) = (0.6, 1.);
+    let rho = arrival / service;
+    // M/M/1: a customer waits iff the server is busy.
+    assert!((erlang_c(1, rho) - rho).abs() < 1e-12);
+    assert!((mmc_mean_wait(arrival, service, 1) - rho / (service - arrival)).abs() < 1e-12);
+    let geometric = Geometric { p: 1. - rho }.pmf(30);
+    assert!(stationary_pmf(arrival, service, 1, None, 30).total_variation(&geometric) < 1e-12);
+
+    // Erlang B is the probability of a full M/M/c/c system.
+    let (servers, load) = (4, 3.);
+    let pmf = stationary_pmf(load, 1., servers, Some(servers), servers + 1);
+    assert!(pmf.is_normalized(1e-12));
+    assert!((pmf.prob(servers) - erlang_b(servers, load)).abs() < 1e-12);
+    // Erlang C is the probability of at least `servers` customers in M/M/c.
+    let pmf = stationary_pmf(load, 1., servers, None, 200);
+    assert!((pmf.survival(servers - 1) - erlang_c(servers, load)).abs() < 1e-9);
+}
+
+#[test]
+fn mm1_queue_length_rounds_50_000() {
+    let rounds = 50_000;
+    let (arrival, service) = (0.5, 1.);
+    let start = QueueStartCondition {
+        arrival,
+        service,
+        servers: 1,
+        capacity: None,
+        warmup: 50,
+    };
+    let n = 15;
+    let mass = sample_repeat_continuous(start, rounds, Untimed(QueueLengthRandomVariable { n }));
+    let simulated = Pmf::from_mass(&mass, rounds);
+    let reference = stationary_pmf(arrival, service, 1, None, n + 1);
+    assert!(simulated.total_variation(&reference) < 0.01);
+}
+
+#[test]
+fn mmc_wait_rounds_50_000() {
+    let rounds = 50_000;
+    let (arrival, service, servers) = (2., 1., 3);
+    let start = || QueueStartCondition {
+        arrival,
+        service,
+        servers,
+        capacity: None,
+        warmup: 100,
+    };
+
+    let mass = sample_repeat_continuous(start(), rounds, Untimed(QueueDelayRandomVariable));
+    let p_delay = prob_mass_func(&mass, rounds)[1];
+    let exact = erlang_c(servers, arrival / service);
+    println!("P(wait > 0) = {} ({})", p_delay, exact);
+    assert_proportion(p_delay, exact, rounds);
+
+    let (width, bins) = (0.25, 12);
+    let rv = QueueWaitRandomVariable { width, bins };
+    let mass = sample_repeat_continuous(start(), rounds, Untimed(rv));
+    let simulated = Pmf::from_mass(&mass, rounds);
+    let survival = |k: usize| mmc_wait_survival(arrival, service, servers, k as f64 * width);
+    let reference = Pmf::new(
+        0,
+        (0..bins)
+            .map(|k| match k {
+                0 => 1. - survival(1),
+                k => survival(k) - survival(k + 1),
+            })
+            .collect(),
+    );
+    assert!(simulated.total_variation(&reference) < 0.01);
+}
+
+#[test]
+fn erlang_b_loss_rounds_20_000() {
+    let rounds = 20_000;
+    let (arrival, service, servers) = (2., 1., 2);
+    let start = QueueStartCondition {
+        arrival,
+        service,
+        servers,
+        capacity: Some(servers),
+        warmup: 50,
+    };
+    let mass = sample_repeat_continuous(start, rounds, Untimed(QueueLossRandomVariable));
+    let p_loss = prob_mass_func(&mass, rounds)[1];
+    let exact = erlang_b(servers, arrival / service);
+    println!("P(loss) = {} ({})", p_loss, exact);
+    assert_proportion(p_loss, exact, rounds);
+}
+
+#[test]
+fn finite_buffer_loss_rounds_20_000() {
+    let rounds = 20_000;
+    let (arrival, service, capacity) = (1., 1., 4);
+    let start = QueueStartCondition {
+        arrival,
+        service,
+        servers: 1,
+        capacity: Some(capacity),
+        warmup: 100,
+    };
+    let mass = sample_repeat_continuous(start, rounds, Untimed(QueueLossRandomVariable));
+    let p_loss = prob_mass_func(&mass, rounds)[1];
+    // With $\lambda = \mu$ all states of M/M/1/K are equally likely.
+    let exact = stationary_pmf(arrival, service, 1, Some(capacity), capacity + 1).prob(capacity);
+    assert!((exact - 0.2).abs() < 1e-12);
+    assert_proportion(p_loss, exact, rounds);
+}
@@ -0,0 +1,165 @@
+use cond_prob_sim::analytic::gambler_s_ruin_win;
+use cond_prob_sim::exact::exact_prob_mass_func;
+use cond_prob_sim::models::random_walk::*;
+use cond_prob_sim::pmf::Pmf;
+use cond_prob_sim::{prob_mass_func, sample_repeat};
+
+mod common;
+
+use common::assert_proportion;
+
+#[test]
+fn lattice_return_rounds_20_000() {
+    let rounds = 20_000;
+    let steps = 20;
+    let start = || LatticeStartCondition {
+        origin: vec![0],
+        absorbing: |x: &[i64]| (x[0] == 0).then_some(0),
+        max_steps: steps,
+    };
+    let exact = simple_walk_return_probability_by(steps);
+
+    let pmf = exact_prob_mass_func(start(), HittingRandomVariable { sets: 1 }, 1e-12);
+    assert!((pmf[0] - exact).abs() < 1e-12);
+
+    let mass = sample_repeat(start(), rounds, HittingRandomVariable { sets: 1 });
+    let p_return = prob_mass_func(&mass, rounds)[0];
+    assert_proportion(p_return, exact, rounds);
+}
+
+#[test]
+fn lattice_returns_rounds_20_000() {
+    let rounds = 20_000;
+    let steps = 30;
+    let start = LatticeStartCondition {
+        origin: vec![0],
+        absorbing: |_: &[i64]| None,
+        max_steps: steps,
+    };
+    let mass = sample_repeat(start, rounds, ReturnsRandomVariable { n: steps / 2 });
+    let returns = Pmf::from_mass(&mass, rounds);
+    let exact = simple_walk_expected_returns(steps);
+    println!("E[returns] = {} ({})", returns.mean(), exact);
+    assert!((returns.mean() - exact).abs() < 5. * returns.mean_standard_error(rounds));
+}
+
+#[test]
+fn lattice_slab_rounds_10_000() {
+    let rounds = 10_000;
+    let (a, b) = (2, 3);
+    // Only the first coordinate matters, and it moves every third step on average.
+    let start = || LatticeStartCondition {
+        origin: vec![0, 0, 0],
+        absorbing: |x: &[i64]| match x[0] {
+            x if x == -a => Some(0),
+            x if x == b => Some(1),
+            _ => None,
+        },
+        max_steps: usize::MAX,
+    };
+
+    let mass = sample_repeat(start(), rounds, HittingRandomVariable { sets: 2 });
+    let p_b = prob_mass_func(&mass, rounds)[1];
+    let exact = gambler_s_ruin_win(a as usize, (a + b) as usize, 0.5);
+    assert_proportion(p_b, exact, rounds);
+
+    let mass = sample_repeat(start(), rounds, FirstPassageRandomVariable { n: 2_000 });
+    let time = Pmf::from_mass(&mass, rounds);
+    let exact = 3. * (a * b) as f64;
+    println!("E[T] = {} ({})", time.mean(), exact);
+    assert!((time.mean() - exact).abs() < 5. * time.mean_standard_error(rounds));
+}
+
+/// A square 0-1-3-2 with a diagonal 1-2 and a tail 3-4-5.
+fn kite() -> Graph {
+    Graph::from_edges(6, &[(0, 1), (0, 2), (1, 2), (1, 3), (2, 3), (3, 4), (4, 5)])
+}
+
+#[test]
+fn graph_hitting_rounds_20_000() {
+    let rounds = 20_000;
+    let absorbing = vec![vec![0], vec![5]];
+    let start = || GraphStartCondition {
+        graph: kite(),
+        absorbing: absorbing.clone(),
+        start: 3,
+        max_steps: usize::MAX,
+    };
+    let exact = kite().hitting_probabilities(&absorbing, 3);
+    assert!((exact.iter().sum::<f64>() - 1.).abs() < 1e-12);
+
+    let mass = sample_repeat(start(), rounds, HittingRandomVariable { sets: 2 });
+    let simulated = prob_mass_func(&mass, rounds);
+    println!("P(hit 0 first) = {} ({})", simulated[0], exact[0]);
+    assert_proportion(simulated[0], exact[0], rounds);
+
+    let start = GraphStartCondition {
+        absorbing: vec![vec![5]],
+        ..start()
+    };
+    let mass = sample_repeat(start, rounds, FirstPassageRandomVariable { n: 1_000 });
+    let time = Pmf::from_mass(&mass, rounds);
+    let exact = kite().expected_hitting_time(&[5], 3);
+    println!("E[T] = {} ({})", time.mean(), exact);
+    assert!((time.mean() - exact).abs() < 5. * time.mean_standard_error(rounds));
+}
+
+#[test]
+fn graph_return_time() {
+    // Kac's formula: the expected return time is $2 |E| / \deg(v)$.
+    let graph = kite();
+    for (v, degree) in [(0, 2.), (1, 3.), (3, 3.), (5, 1.)] {
+        let exact = 2. * 7. / degree;
+        assert!((graph.expected_hitting_time(&[v], v) - exact).abs() < 1e-9);
+    }
+
+    let rounds = 20_000;
+    let n = 8;
+    let start = GraphStartCondition {
+        graph: Graph::cycle(n),
+        absorbing: vec![vec![0]],
+        start: 0,
+        max_steps: usize::MAX,
+    };
+    let mass = sample_repeat(start, rounds, FirstPassageRandomVariable { n: 2_000 });
+    let time = Pmf::from_mass(&mass, rounds);
+    assert!((time.mean() - n as f64).abs() < 5. * time.mean_standard_error(rounds));
+}
+
+#[test]
+fn path_is_gambler_s_ruin() {
+    let n = 6;
+    let edges: Vec<_> = (0..n).map(|v| (v, v + 1)).collect();
+    let path = Graph::from_edges(n + 1, &edges);
+    for i in 1..n {
+        let exact = path.hitting_probabilities(&[vec![0], vec![n]], i);
+        assert!((exact[1] - gambler_s_ruin_win(i, n, 0.5)).abs() < 1e-9);
+        let time = path.expected_hitting_time(&[0, n], i);
+        assert!((time - (i * (n - i)) as f64).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn unreachable_target() {
+    // Vertex 2 only leads to itself, and vertex 3 is cut off from the others.
+    let graph = Graph::from_weights(vec![
+        vec![(1., 1)],
+        vec![(1., 0), (1., 2)],
+        vec![(1., 2)],
+        vec![(1., 3)],
+    ]);
+    assert_eq!(graph.expected_hitting_time(&[3], 0), f64::INFINITY);
+    // The walk from 0 gets stuck in 2 with probability one.
+    assert_eq!(graph.expected_hitting_time(&[0], 1), f64::INFINITY);
+    // Starting in 2 it stays there.
+    assert_eq!(graph.expected_hitting_time(&[2], 2), 1.);
+    assert!((graph.expected_hitting_time(&[2], 0) - 4.).abs() < 1e-9);
+}
+
+#[test]
+fn polya_return_probability_by_dimension() {
+    assert_eq!(polya_return_probability(1), Some(1.));
+    assert_eq!(polya_return_probability(2), Some(1.));
+    assert!((polya_return_probability(3).unwrap() - 0.340537).abs() < 1e-6);
+    assert_eq!(polya_return_probability(4), None);
+}
@@ -0,0 +1,43 @@
+use cond_prob_sim::render::{bar_chart, write_svg, Charset};
+use cond_prob_sim::stats::ConfidenceInterval;
+
+#[test]
+fn ascii_bar_chart_with_reference() {
+    let pmf = [0.25, 0.5, 0.25];
+    let reference = [0.5, 0.25, 0.25];
+    let chart = bar_chart(&pmf, Some(&reference), 8, Charset::Ascii);
+    println!("{}", chart);
+    let lines: Vec<&str> = chart.lines().collect();
+    assert_eq!(lines[0], "0 ####    | 0.2500 (0.5000)");
+    assert_eq!(lines[1], "1 ####|###  0.5000 (0.2500)");
+    assert_eq!(lines[2], "2 ####|     0.2500 (0.2500)");
+}
+
+#[test]
+fn unicode_bar_chart() {
+    let chart = bar_chart(&[0.5, 1.0], None, 1, Charset::Unicode);
+    println!("{}", chart);
+    assert_eq!(chart, "0 ▌  0.5000\n1 █  1.0000\n");
+}
+
+#[test]
+fn svg_with_error_bars() {
+    let pmf = [0.2, 0.8];
+    let intervals = [
+        ConfidenceInterval {
+            lower: 0.1,
+            upper: 0.3,
+        },
+        ConfidenceInterval {
+            lower: 0.7,
+            upper: 0.9,
+        },
+    ];
+    let mut svg = Vec::new();
+    write_svg(&mut svg, &pmf, Some(&[0.25, 0.75]), Some(&intervals)).unwrap();
+    let svg = String::from_utf8(svg).unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert_eq!(svg.matches("fill=\"#4c78a8\"").count(), 2);
+    assert_eq!(svg.matches("stroke=\"#e45756\"").count(), 2);
+}
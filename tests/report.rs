@@ -0,0 +1,81 @@
+use cond_prob_sim::models::binomial::*;
+use cond_prob_sim::report::SimulationReport;
+use cond_prob_sim::rng::seed;
+use cond_prob_sim::sample_repeat;
+
+fn run(rounds: usize) -> SimulationReport {
+    let n = 4;
+    let p = 0.5;
+    seed(42);
+    let counts = sample_repeat(BinStartCondition { n, p }, rounds, BinRandomVariable { n });
+    let mut report = SimulationReport::new("binomial", counts, rounds, 0.95);
+    report.parameters.insert("n".into(), n as f64);
+    report.parameters.insert("p".into(), p);
+    report.seed = Some(42);
+    report
+}
+
+#[test]
+fn seeded_runs_are_reproducible() {
+    assert_eq!(run(1_000), run(1_000));
+}
+
+#[test]
+fn csv() {
+    let report = run(1_000);
+    let mut csv = Vec::new();
+    report.write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    println!("{}", csv);
+    let (comments, lines): (Vec<&str>, Vec<&str>) = csv.lines().partition(|l| l.starts_with('#'));
+    assert_eq!(
+        comments,
+        [
+            "# model: binomial",
+            "# n = 4",
+            "# p = 0.5",
+            "# seed: 42",
+            "# rounds: 1000",
+            "# untabulated: 0",
+            "# confidence: 0.95"
+        ]
+    );
+    assert_eq!(lines.len(), 1 + 5);
+    assert_eq!(lines[0], "value,count,probability,ci_lower,ci_upper");
+    assert!(lines[1].starts_with(&format!("0,{},", report.counts[0])));
+}
+
+#[test]
+fn untabulated_rounds_have_no_moments() {
+    let report = SimulationReport::new("truncated", vec![3, 2], 10, 0.95);
+    assert_eq!(report.untabulated, 5);
+    assert_eq!(report.pmf, [0.3, 0.2]);
+    assert_eq!(report.mean, None);
+    assert_eq!(report.variance, None);
+    assert_eq!(report.mean_interval, None);
+    let report = SimulationReport::new("complete", vec![3, 2], 5, 0.95);
+    assert_eq!(report.untabulated, 0);
+    assert_eq!(report.mean, Some(0.4));
+}
+
+#[test]
+#[should_panic(expected = "positive number of rounds")]
+fn more_counts_than_rounds() {
+    SimulationReport::new("binomial", vec![3, 2], 4, 0.95);
+}
+
+#[test]
+#[should_panic(expected = "positive number of rounds")]
+fn no_rounds() {
+    SimulationReport::new("binomial", vec![0, 0], 0, 0.95);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json() {
+    let report = run(1_000);
+    let mut json = Vec::new();
+    report.write_json(&mut json).unwrap();
+    let parsed: SimulationReport = serde_json::from_slice(&json).unwrap();
+    assert_eq!(parsed, report);
+}
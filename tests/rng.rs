@@ -0,0 +1,19 @@
+use rand::Rng;
+
+use cond_prob_sim::rng;
+
+#[test]
+fn with_seed_restores_the_generator() {
+    rng::seed(5);
+    let expected: Vec<u64> = rng::with_rng(|rng| (0..3).map(|_| rng.gen()).collect());
+
+    rng::seed(5);
+    let first: u64 = rng::with_rng(|rng| rng.gen());
+    let inner = rng::with_seed(9, || rng::with_rng(|rng| rng.gen::<u64>()));
+    let rest: Vec<u64> = rng::with_rng(|rng| (0..2).map(|_| rng.gen()).collect());
+    assert_eq!([vec![first], rest].concat(), expected);
+
+    // The closure sees the reseeded generator.
+    rng::seed(9);
+    assert_eq!(rng::with_rng(|rng| rng.gen::<u64>()), inner);
+}
@@ -0,0 +1,63 @@
+use rand::Rng;
+
+use cond_prob_sim::analytic::gambler_s_ruin_win;
+use cond_prob_sim::models::binomial::*;
+use cond_prob_sim::models::gambler_s_ruin::*;
+use cond_prob_sim::rng;
+use cond_prob_sim::sensitivity::{finite_difference, score_function};
+
+fn win(outcome: GROutcome) -> f64 {
+    match outcome {
+        GROutcome::AWin => 1.,
+        GROutcome::BWin => 0.,
+    }
+}
+
+#[test]
+fn gambler_s_ruin_rounds_20_000() {
+    let rounds = 20_000;
+    let (i, n, p) = (3, 10, 0.45);
+    let start = |p| GRStartCondition { i, n, p };
+    let h = 1e-6;
+    let expected = (gambler_s_ruin_win(i, n, p + h) - gambler_s_ruin_win(i, n, p - h)) / (2. * h);
+
+    let score = score_function(start, p, 1e-4, rounds, win);
+    println!("score function: {:?} ({})", score, expected);
+    assert!((score.estimate - expected).abs() < 5. * score.standard_error);
+
+    let difference = finite_difference(start, p, 0.01, rounds, win);
+    println!("finite difference: {:?} ({})", difference, expected);
+    assert!((difference.estimate - expected).abs() < 5. * difference.standard_error + 0.01);
+    assert!(difference.interval(0.95).lower < difference.interval(0.95).upper);
+}
+
+#[test]
+fn binomial_mean_rounds_20_000() {
+    let rounds = 20_000;
+    let n = 10;
+    let start = |p| BinStartCondition { n, p };
+    let successes = |outcome: BinOutcome| outcome.successes as f64;
+
+    // $\frac{d}{dp} np = n$
+    let score = score_function(start, 0.3, 1e-4, rounds, successes);
+    assert!((score.estimate - n as f64).abs() < 5. * score.standard_error);
+    let difference = finite_difference(start, 0.3, 0.01, rounds, successes);
+    assert!((difference.estimate - n as f64).abs() < 5. * difference.standard_error);
+    // Common random numbers make the finite difference far less noisy.
+    assert!(difference.standard_error < score.standard_error);
+}
+
+#[test]
+fn finite_difference_only_draws_the_seeds() {
+    let rounds = 100;
+    let start = |p| BinStartCondition { n: 10, p };
+    let successes = |outcome: BinOutcome| outcome.successes as f64;
+
+    rng::seed(11);
+    // The estimate draws one seed per round and nothing else.
+    let expected = rng::with_rng(|rng| (0..=rounds).map(|_| rng.gen::<u64>()).last().unwrap());
+
+    rng::seed(11);
+    finite_difference(start, 0.3, 0.01, rounds, successes);
+    assert_eq!(rng::with_rng(|rng| rng.gen::<u64>()), expected);
+}
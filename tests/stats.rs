@@ -0,0 +1,25 @@
+use cond_prob_sim::models::binomial::*;
+use cond_prob_sim::stats::{mean_interval, normal_quantile, wilson_interval};
+use cond_prob_sim::{prob_mass_func, sample_repeat};
+
+#[test]
+fn normal_quantiles() {
+    assert!((normal_quantile(0.5)).abs() < 1e-9);
+    assert!((normal_quantile(0.975) - 1.959963984540054).abs() < 1e-6);
+    assert!((normal_quantile(0.01) + 2.326347874040841).abs() < 1e-6);
+}
+
+#[test]
+fn rounds_100_000_n_10_p_0_2() {
+    let rounds = 100_000;
+    let n = 10;
+    let p = 0.2;
+    let mass = sample_repeat(BinStartCondition { n, p }, rounds, BinRandomVariable { n });
+    let ci = wilson_interval(mass[0], rounds, 0.999);
+    println!("P(X = 0) CI: {:?}", ci);
+    assert!(ci.contains(0.8_f64.powi(10)));
+    let prob_mass_func = prob_mass_func(&mass, rounds);
+    let ci = mean_interval(&prob_mass_func, rounds, 0.999);
+    println!("E[X] CI: {:?}", ci);
+    assert!(ci.contains(n as f64 * p));
+}
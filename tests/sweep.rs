@@ -0,0 +1,66 @@
+use cond_prob_sim::models::binomial::*;
+use cond_prob_sim::sweep::{sweep, Grid, Sweep};
+
+fn run(rounds: usize) -> Sweep {
+    let grid = Grid::new().axis("n", [5., 10.]).axis("p", [0.2, 0.5, 0.8]);
+    sweep(
+        "binomial",
+        &grid,
+        rounds,
+        0.999,
+        42,
+        |point| BinStartCondition {
+            n: point[0] as usize,
+            p: point[1],
+        },
+        |point| BinRandomVariable {
+            n: point[0] as usize,
+        },
+    )
+}
+
+#[test]
+fn grid_points() {
+    let grid = Grid::new().axis("a", [1., 2.]).axis("b", [3., 4., 5.]);
+    assert_eq!(grid.len(), 6);
+    assert_eq!(grid.points()[0], vec![1., 3.]);
+    assert_eq!(grid.points()[1], vec![1., 4.]);
+    assert_eq!(grid.points()[5], vec![2., 5.]);
+    assert!(Grid::new().axis("a", []).is_empty());
+}
+
+#[test]
+fn binomial_rounds_10_000() {
+    let sweep = run(10_000);
+    assert_eq!(sweep.reports.len(), 6);
+    for report in &sweep.reports {
+        let expected = report.parameters["n"] * report.parameters["p"];
+        println!("{:?}: {:?} ({})", report.parameters, report.mean, expected);
+        assert!(report.mean_interval.unwrap().contains(expected));
+    }
+    assert_eq!(sweep, run(10_000));
+}
+
+#[test]
+fn csv() {
+    let sweep = run(1_000);
+    let mut csv = Vec::new();
+    sweep.write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 1 + 6);
+    assert_eq!(
+        lines[0],
+        "n,p,seed,rounds,untabulated,mean,mean_ci_lower,mean_ci_upper,variance"
+    );
+    assert!(lines[1].starts_with("5,0.2,42,1000,0,"));
+    assert!(lines[6].starts_with("10,0.8,47,1000,0,"));
+
+    let mut csv = Vec::new();
+    sweep.write_pmf_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 1 + 3 * 6 + 3 * 11);
+    assert_eq!(lines[0], "n,p,value,count,probability,ci_lower,ci_upper");
+    assert!(lines[1].starts_with("5,0.2,0,"));
+}
@@ -0,0 +1,112 @@
+use cond_prob_sim::analytic::Distribution;
+use cond_prob_sim::exact::exact_prob_mass_func;
+use cond_prob_sim::models::urn::*;
+use cond_prob_sim::pmf::Pmf;
+use cond_prob_sim::sample_repeat;
+
+#[test]
+fn polya_rounds_100_000_n_10() {
+    let rounds = 100_000;
+    let n = 10;
+    let start = || PolyaStartCondition {
+        red: 1,
+        black: 2,
+        added: 1,
+        n,
+    };
+
+    let mass = sample_repeat(start(), rounds, UrnRedDrawsRandomVariable { n });
+    let simulated = Pmf::from_mass(&mass, rounds);
+    let reference = polya_red_draws(1, 2, 1, n).pmf(n + 1);
+    assert!(simulated.total_variation(&reference) < 0.01);
+
+    // The number of red balls is the initial one plus one per red draw.
+    let exact = Pmf::from(exact_prob_mass_func(
+        start(),
+        UrnRedRandomVariable { n: n + 1 },
+        0.,
+    ));
+    assert!(exact.total_variation(&reference.shift(1)) < 1e-12);
+}
+
+#[test]
+fn polya_red_draws_exact() {
+    let n = 8;
+    for (red, black, added) in [(2, 3, 2), (3, 1, 1), (1, 1, 3)] {
+        let start = PolyaStartCondition {
+            red,
+            black,
+            added,
+            n,
+        };
+        let exact = Pmf::from(exact_prob_mass_func(
+            start,
+            UrnRedDrawsRandomVariable { n },
+            0.,
+        ));
+        let reference = polya_red_draws(red, black, added, n).pmf(n + 1);
+        assert!(exact.total_variation(&reference) < 1e-12);
+    }
+}
+
+#[test]
+fn friedman_expected_composition() {
+    let rounds = 100_000;
+    let n = 12;
+    let start = || FriedmanStartCondition {
+        red: 5,
+        black: 1,
+        same: 2,
+        opposite: 1,
+        n,
+    };
+    let rv = || UrnRedRandomVariable { n: 6 + 3 * n };
+
+    let expected = friedman_expected_red(5, 1, 2, 1, n);
+    let exact = Pmf::from(exact_prob_mass_func(start(), rv(), 0.));
+    assert!((exact.mean() - expected).abs() < 1e-9);
+
+    let mass = sample_repeat(start(), rounds, rv());
+    let simulated = Pmf::from_mass(&mass, rounds);
+    println!("E[R_n] = {} ({})", simulated.mean(), expected);
+    assert!((simulated.mean() - expected).abs() < 5. * simulated.mean_standard_error(rounds));
+
+    // The composition is drawn towards balance.
+    let total = (6 + 3 * n) as f64;
+    assert!(expected / total < 5. / 6.);
+    assert!(expected / total > 0.5);
+}
+
+#[test]
+fn ehrenfest_rounds_100_000_total_10() {
+    let rounds = 100_000;
+    let total = 10;
+    let start = |lazy, n| EhrenfestStartCondition {
+        in_a: total,
+        total,
+        lazy,
+        n,
+    };
+    let stationary = ehrenfest_stationary(total).pmf(total + 1);
+
+    for n in [1, 5, 20] {
+        let exact = Pmf::from(exact_prob_mass_func(
+            start(false, n),
+            EhrenfestRandomVariable { total },
+            0.,
+        ));
+        assert!((exact.mean() - ehrenfest_expected_in_a(total, total, false, n)).abs() < 1e-9);
+        // Without laziness the parity of the count alternates.
+        assert!(exact
+            .values()
+            .all(|(x, p)| p == 0. || (total - x + n) % 2 == 0));
+    }
+
+    let mass = sample_repeat(start(true, 100), rounds, EhrenfestRandomVariable { total });
+    let simulated = Pmf::from_mass(&mass, rounds);
+    assert!(simulated.total_variation(&stationary) < 0.01);
+    assert!(
+        (simulated.mean() - ehrenfest_expected_in_a(total, total, true, 100)).abs()
+            < 5. * simulated.mean_standard_error(rounds)
+    );
+}